@@ -1,7 +1,7 @@
 //! Miscellaneous assembly instructions
 
 #[cfg(cortex_m)]
-use core::arch::asm;
+use core::arch::{asm, global_asm};
 use core::sync::atomic::{compiler_fence, Ordering};
 
 /// Puts the processor in Debug state. Debuggers can pick this up as a "breakpoint".
@@ -116,6 +116,26 @@ pub fn dsb() {
     compiler_fence(Ordering::SeqCst);
 }
 
+/// Barrier required after writing a system configuration register or maintaining the caches
+///
+/// Issues a DSB followed by an ISB: the DSB waits until the write (e.g. to VTOR or CCR, or a
+/// cache maintenance operation) has completed, and the ISB makes sure the instructions that follow
+/// are fetched and executed with the new configuration in effect.
+#[inline(always)]
+pub fn barrier_after_config_write() {
+    dsb();
+    isb();
+}
+
+/// Barrier required after writing the CONTROL register
+///
+/// Issues an ISB, so that the instructions that follow execute with the new CONTROL value, e.g.
+/// use the newly selected stack pointer or privilege level (see Application Note 321).
+#[inline(always)]
+pub fn barrier_after_control_write() {
+    isb();
+}
+
 /// Data Memory Barrier
 ///
 /// Ensures that all explicit memory accesses that appear in program order before the `DMB`
@@ -240,6 +260,25 @@ pub unsafe fn semihosting_syscall(mut nr: u32, arg: u32) -> u32 {
     nr
 }
 
+/// Semihosting call.
+///
+/// Places `op` in R0 and `arg` in R1, executes `bkpt 0xAB` and returns the value left in R0 by
+/// the debugger. This is enough to perform simple operations (like `SYS_WRITE0`) without
+/// depending on the `cortex-m-semihosting` crate.
+///
+/// # Safety
+///
+/// `arg` must point to a parameter block that is valid for the requested `op`, as described in
+/// the ARM semihosting specification.
+///
+/// **NOTE** If no semihosting-aware debugger is attached the processor will either hang in Debug
+/// state or raise a HardFault exception.
+#[cfg(cortex_m)]
+#[inline(always)]
+pub unsafe fn semihosting_call(op: u32, arg: *mut ()) -> u32 {
+    semihosting_syscall(op, arg as u32)
+}
+
 /// Bootstrap.
 ///
 /// Clears CONTROL.SPSEL (setting the main stack to be the active stack),
@@ -292,3 +331,101 @@ pub unsafe fn bootload(vector_table: *const u32) -> ! {
     let rv = core::ptr::read_volatile(vector_table.offset(1));
     bootstrap(msp as *const u32, rv as *const u32);
 }
+
+// Context switch trampoline used by the `pendsv_context_switch!` macro.
+//
+// Saves R4-R11 of the current task below its hardware-stacked exception frame on the process
+// stack, calls `__cortex_m_context_switch(old_psp) -> new_psp` (defined by the macro) and
+// restores R4-R11 of the next task from its process stack.
+//
+// The saved registers are laid out in ascending register order from the returned stack pointer
+// upwards: R4, R5, ..., R11, immediately followed by the hardware-stacked frame (R0-R3, R12, LR,
+// PC, xPSR).
+#[cfg(all(cortex_m, not(any(armv6m, armv8m_base))))]
+global_asm!(
+    ".section .text.__cortex_m_pendsv_trampoline, \"ax\"
+     .global __cortex_m_pendsv_trampoline
+     .type __cortex_m_pendsv_trampoline,%function
+     .thumb_func
+     __cortex_m_pendsv_trampoline:
+     mrs r0, psp
+     stmdb r0!, {{r4-r11}}
+     push {{r2, lr}}
+     bl __cortex_m_context_switch
+     pop {{r2, lr}}
+     ldmia r0!, {{r4-r11}}
+     msr psp, r0
+     bx lr"
+);
+
+// ARMv6-M and ARMv8-M Baseline can only load and store multiple low registers, so R8-R11 are
+// moved through R4-R7. The resulting stack layout is the same as above.
+#[cfg(all(cortex_m, any(armv6m, armv8m_base)))]
+global_asm!(
+    ".section .text.__cortex_m_pendsv_trampoline, \"ax\"
+     .global __cortex_m_pendsv_trampoline
+     .type __cortex_m_pendsv_trampoline,%function
+     .thumb_func
+     __cortex_m_pendsv_trampoline:
+     mrs r0, psp
+     subs r0, #32
+     mov r1, r0
+     stmia r1!, {{r4-r7}}
+     mov r4, r8
+     mov r5, r9
+     mov r6, r10
+     mov r7, r11
+     stmia r1!, {{r4-r7}}
+     push {{r2, lr}}
+     bl __cortex_m_context_switch
+     pop {{r2, r3}}
+     mov lr, r3
+     adds r0, #16
+     ldmia r0!, {{r4-r7}}
+     mov r8, r4
+     mov r9, r5
+     mov r10, r6
+     mov r11, r7
+     msr psp, r0
+     subs r0, #32
+     ldmia r0!, {{r4-r7}}
+     bx lr"
+);
+
+// FPU-aware variant of the context switch trampoline, used by the `pendsv_context_switch_fpu!`
+// macro.
+//
+// In addition to R4-R11 this saves the EXC_RETURN value of the current task and, if the task
+// has an active floating-point context (EXC_RETURN.FType == 0), the callee-saved FP registers
+// S16-S31. The resulting layout, from the stack pointer upwards, is: R4, ..., R11, EXC_RETURN,
+// then S16, ..., S31 (only if FType == 0), then the hardware-stacked (extended) frame.
+//
+// NOTE `global_asm!` is assembled without the target's FPU features, hence the `.fpu` directive;
+// the instructions used here have the same encoding on every Cortex-M FPU.
+#[cfg(all(cortex_m, has_fpu))]
+global_asm!(
+    ".section .text.__cortex_m_pendsv_trampoline_fpu, \"ax\"
+     .fpu fpv4-sp-d16
+     .global __cortex_m_pendsv_trampoline_fpu
+     .type __cortex_m_pendsv_trampoline_fpu,%function
+     .thumb_func
+     __cortex_m_pendsv_trampoline_fpu:
+     mrs r0, psp
+     tst lr, #0x10
+     it eq
+     vstmdbeq r0!, {{s16-s31}}
+     stmdb r0!, {{r4-r11, lr}}
+     bl __cortex_m_context_switch
+     ldmia r0!, {{r4-r11, lr}}
+     tst lr, #0x10
+     it eq
+     vldmiaeq r0!, {{s16-s31}}
+     msr psp, r0
+     bx lr"
+);
+
+// Referenced by the `pendsv_context_switch_fpu!` macro so that using it on a target without an
+// FPU is a compile time error rather than a link time error.
+#[doc(hidden)]
+#[cfg(any(has_fpu, native))]
+pub const __HAS_FPU: () = ();
@@ -37,7 +37,7 @@ use bitfield::bitfield;
 
 /// Memory access behaviour: determine which privilege execution mode is used and which Memory
 /// Protection Unit (MPU) is used.
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum AccessType {
     /// Access using current privilege level and reading from current security state MPU.
     /// Uses the TT instruction.
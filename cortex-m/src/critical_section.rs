@@ -1,16 +1,13 @@
 use critical_section::{set_impl, Impl, RawRestoreState};
 
 use crate::interrupt;
-use crate::register::primask;
 
 struct SingleCoreCriticalSection;
 set_impl!(SingleCoreCriticalSection);
 
 unsafe impl Impl for SingleCoreCriticalSection {
     unsafe fn acquire() -> RawRestoreState {
-        let was_active = primask::read().is_active();
-        interrupt::disable();
-        was_active
+        interrupt::disable_and_save().is_active()
     }
 
     unsafe fn release(was_active: RawRestoreState) {
@@ -0,0 +1,61 @@
+//! Atomic read-modify-write operations emulated with critical sections
+//!
+//! ARMv6-M devices (Cortex-M0 and Cortex-M0+) lack the load/store-exclusive instructions, so
+//! `core::sync::atomic` only provides `load` and `store` on those targets. The functions in this
+//! module provide the missing read-modify-write operations on an `AtomicU32` by performing the
+//! load and store with interrupts disabled (see [`interrupt::free`](crate::interrupt::free)).
+//!
+//! **NOTE** These operations are *not* lock-free: every call masks all interrupts with
+//! configurable priority for a few cycles. Don't use them where interrupt latency is critical.
+//! Like `interrupt::free`, they are only correct on single-core systems.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::interrupt;
+
+/// Adds `val` to the current value, wrapping around on overflow, and returns the previous value
+#[inline]
+pub fn fetch_add(atomic: &AtomicU32, val: u32) -> u32 {
+    fetch_update(atomic, |old| old.wrapping_add(val))
+}
+
+/// Bitwise "and" with the current value; returns the previous value
+#[inline]
+pub fn fetch_and(atomic: &AtomicU32, val: u32) -> u32 {
+    fetch_update(atomic, |old| old & val)
+}
+
+/// Bitwise "or" with the current value; returns the previous value
+#[inline]
+pub fn fetch_or(atomic: &AtomicU32, val: u32) -> u32 {
+    fetch_update(atomic, |old| old | val)
+}
+
+/// Stores `new` if the current value is equal to `current`
+///
+/// Returns `Ok` with the previous value if the value was updated, otherwise `Err` with the
+/// current value.
+#[inline]
+pub fn compare_exchange(atomic: &AtomicU32, current: u32, new: u32) -> Result<u32, u32> {
+    interrupt::free(|| {
+        let old = atomic.load(Ordering::Relaxed);
+        if old == current {
+            atomic.store(new, Ordering::Relaxed);
+            Ok(old)
+        } else {
+            Err(old)
+        }
+    })
+}
+
+#[inline]
+fn fetch_update<F>(atomic: &AtomicU32, f: F) -> u32
+where
+    F: FnOnce(u32) -> u32,
+{
+    interrupt::free(|| {
+        let old = atomic.load(Ordering::Relaxed);
+        atomic.store(f(old), Ordering::Relaxed);
+        old
+    })
+}
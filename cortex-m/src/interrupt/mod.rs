@@ -0,0 +1,441 @@
+//! Interrupts
+
+#[cfg(cortex_m)]
+use core::arch::asm;
+#[cfg(cortex_m)]
+use core::sync::atomic::compiler_fence;
+#[cfg(any(cortex_m, all(test, debug_assertions)))]
+use core::sync::atomic::Ordering;
+
+#[cfg(any(cortex_m, test))]
+use crate::register::primask::Primask;
+
+// Host stand-ins for the PRIMASK accesses of `free_primask`
+#[cfg(all(test, not(cortex_m)))]
+use self::test::{disable_and_save, restore};
+
+pub mod atomic;
+
+mod spsc;
+
+pub use self::spsc::SpscCell;
+
+/// Trait for enums of external interrupt numbers.
+///
+/// This trait should be implemented by a peripheral access crate (PAC)
+/// on its enum of available external interrupts for a specific device.
+/// Each variant must convert to a u16 of its interrupt number,
+/// which is its exception number - 16.
+///
+/// # Safety
+///
+/// This trait must only be implemented on enums of device interrupts. Each
+/// enum variant must represent a distinct value (no duplicates are permitted),
+/// and must always return the same value (do not change at runtime).
+///
+/// These requirements ensure safe nesting of critical sections.
+pub unsafe trait InterruptNumber: Copy {
+    /// Return the interrupt number associated with this variant.
+    ///
+    /// See trait documentation for safety requirements.
+    fn number(self) -> u16;
+}
+
+/// Returns `true` if an exception with priority `prio` is masked while BASEPRI is `basepri`
+///
+/// `prio` and `basepri` are hardware priority values, as used by
+/// [`NVIC::set_priority`](crate::peripheral::NVIC::set_priority) and
+/// [`register::basepri::write`](crate::register::basepri::write). `prio_bits` is the number of
+/// priority bits implemented by the device; the unimplemented low bits of both values are
+/// ignored, as the hardware does.
+///
+/// A `basepri` of `0` disables masking. Otherwise, every exception whose priority value is
+/// greater than or equal to `basepri` (i.e. whose urgency is lower or the same) is masked.
+///
+/// *NOTE* This ignores priority grouping: the hardware compares BASEPRI against the group
+/// priority only, so an exception that differs from `basepri` only in its subpriority bits is
+/// masked even if this function returns `false`.
+#[cfg(all(not(armv6m), not(armv8m_base)))]
+#[inline]
+pub fn would_be_masked(basepri: u8, prio: u8, prio_bits: u8) -> bool {
+    let mask = !(0xffu32 >> u32::from(prio_bits.min(8))) as u8;
+    let basepri = basepri & mask;
+
+    basepri != 0 && prio & mask >= basepri
+}
+
+/// Nesting depth of the PRIMASK-based `free` critical sections, used to catch calls to
+/// [`enable`] inside them in debug builds
+#[cfg(all(any(cortex_m, test), debug_assertions))]
+static FREE_DEPTH: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// Disables all interrupts in the current core.
+#[cfg(cortex_m)]
+#[inline]
+pub fn disable() {
+    unsafe {
+        asm!("cpsid i", options(nomem, nostack, preserves_flags));
+    }
+
+    // Ensure no subsequent memory accesses are reordered to before interrupts are disabled.
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Enables all the interrupts in the current core.
+///
+/// # Safety
+///
+/// - Do not call this function inside a critical section.
+///
+/// # Panics
+///
+/// In debug builds, panics if called from the closure passed to [`free`] (when `free` is based on
+/// PRIMASK), since that ends the critical section early.
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn enable() {
+    #[cfg(debug_assertions)]
+    assert!(
+        FREE_DEPTH.load(Ordering::Relaxed) == 0,
+        "interrupt::enable() called inside interrupt::free()"
+    );
+
+    // Ensure no preceeding memory accesses are reordered to after interrupts are enabled.
+    compiler_fence(Ordering::SeqCst);
+
+    asm!("cpsie i", options(nomem, nostack, preserves_flags));
+}
+
+/// Returns `true` if interrupts are enabled in the current core, i.e. PRIMASK is clear
+///
+/// This is useful to check preconditions, e.g. `debug_assert!(interrupt::enabled())` in a
+/// function that must not be called from a critical section.
+#[cfg(cortex_m)]
+#[inline]
+pub fn enabled() -> bool {
+    crate::register::primask::read().is_active()
+}
+
+/// Returns the current BASEPRI value, i.e. the priority at or below which exceptions are masked
+///
+/// `0` means that BASEPRI doesn't mask anything; see [`would_be_masked`] to check a priority
+/// against the returned value.
+#[cfg(all(cortex_m, not(armv6m), not(armv8m_base)))]
+#[inline]
+pub fn effective_mask() -> u8 {
+    crate::register::basepri::read()
+}
+
+/// Disables all interrupts in the current core and returns the previous PRIMASK state.
+///
+/// Unlike reading PRIMASK with [`primask::read`](crate::register::primask::read) before calling
+/// [`disable`], the state is captured by the same instruction sequence that disables interrupts,
+/// so it can't be changed by an interrupt in between. Pass the returned value to [`restore`] to
+/// leave the critical section.
+#[cfg(cortex_m)]
+#[inline]
+pub fn disable_and_save() -> Primask {
+    let r: u32;
+    unsafe {
+        asm!(
+            "mrs {}, PRIMASK",
+            "cpsid i",
+            out(reg) r,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+
+    // Ensure no subsequent memory accesses are reordered to before interrupts are disabled.
+    compiler_fence(Ordering::SeqCst);
+
+    Primask::from_bits(r)
+}
+
+/// Restores the PRIMASK state returned by [`disable_and_save`].
+///
+/// Interrupts are only re-enabled if they were enabled when `disable_and_save` was called.
+///
+/// # Safety
+///
+/// - `primask` must come from the matching [`disable_and_save`] call, and critical sections must
+///   be restored in the reverse order in which they were entered.
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn restore(primask: Primask) {
+    if primask.is_active() {
+        enable();
+    }
+}
+
+/// Execute closure `f` with interrupts disabled in the current core.
+///
+/// This method does not synchronise multiple cores and may disable required
+/// interrupts on some platforms; see the `critical-section` crate for a cross-platform
+/// way to enter a critical section which provides a `CriticalSection` token.
+///
+/// This crate provides an implementation for `critical-section` suitable for single-core systems,
+/// based on disabling all interrupts. It can be enabled with the `critical-section-single-core` feature.
+///
+/// With the `basepri-critical-section` feature, on ARMv7-M and ARMv8-M Mainline, this function
+/// raises BASEPRI to the priority set with [`critical_section_basepri!`](crate::critical_section_basepri)
+/// instead of disabling all interrupts; see the crate level documentation.
+#[cfg(cortex_m)]
+#[inline]
+pub fn free<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(not(all(feature = "basepri-critical-section", not(armv6m), not(armv8m_base))))]
+    {
+        free_primask(f)
+    }
+
+    #[cfg(all(feature = "basepri-critical-section", not(armv6m), not(armv8m_base)))]
+    {
+        use crate::register::{basepri, basepri_max};
+
+        extern "Rust" {
+            static __cortex_m_critical_section_basepri: u8;
+        }
+
+        // Restores BASEPRI when dropped, so that it's restored even if `f` unwinds
+        struct Guard(u8);
+
+        impl Drop for Guard {
+            #[inline]
+            fn drop(&mut self) {
+                // Ensure no preceeding memory accesses are reordered to after BASEPRI is restored.
+                compiler_fence(Ordering::SeqCst);
+
+                unsafe { basepri::write(self.0) };
+            }
+        }
+
+        let _guard = Guard(basepri::read());
+        // NOTE(unsafe) the static is defined by the `critical_section_basepri!` macro and never
+        // written
+        basepri_max::write(unsafe { __cortex_m_critical_section_basepri });
+
+        // Ensure no subsequent memory accesses are reordered to before BASEPRI is raised.
+        compiler_fence(Ordering::SeqCst);
+
+        f()
+    }
+}
+
+/// PRIMASK-based implementation of [`free`]
+///
+/// Also compiled for the host unit tests, where PRIMASK is emulated.
+#[cfg(any(
+    test,
+    all(
+        cortex_m,
+        not(all(feature = "basepri-critical-section", not(armv6m), not(armv8m_base)))
+    )
+))]
+#[inline]
+fn free_primask<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    // Restores PRIMASK when dropped, so that interrupts are re-enabled even if `f` unwinds
+    struct Guard(Primask);
+
+    impl Drop for Guard {
+        #[inline]
+        fn drop(&mut self) {
+            #[cfg(debug_assertions)]
+            FREE_DEPTH.store(FREE_DEPTH.load(Ordering::Relaxed) - 1, Ordering::Relaxed);
+
+            // If the interrupts were active before our `disable_and_save` call, then
+            // re-enable them. Otherwise, keep them disabled
+            unsafe { restore(self.0) };
+        }
+    }
+
+    let _guard = Guard(disable_and_save());
+
+    // NOTE a load followed by a store, as ARMv6-M has no atomic read-modify-write; interrupts
+    // are disabled here
+    #[cfg(debug_assertions)]
+    FREE_DEPTH.store(FREE_DEPTH.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+
+    f()
+}
+
+/// Execute closure `f` with both PRIMASK and FAULTMASK set, restoring both afterwards.
+///
+/// Like [`free`], but also masks the HardFault, MemManage, BusFault and UsageFault handlers
+/// while `f` runs (only NMI can preempt it) and, on exit, leaves FAULTMASK in the state it had on
+/// entry. This makes it safe to nest a critical section inside a fault handler, or inside a
+/// section of code running with FAULTMASK set, without unmasking faults on exit.
+///
+/// *NOTE* The processor ignores attempts to set FAULTMASK from the NMI and HardFault handlers,
+/// and FAULTMASK is cleared automatically on return from any exception other than NMI.
+///
+/// On ARMv6-M and ARMv8-M Baseline, which don't have FAULTMASK, this is the same as [`free`].
+#[cfg(cortex_m)]
+#[inline]
+pub fn free_preserve_all<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    {
+        let primask: u32;
+        let faultmask: u32;
+        unsafe {
+            asm!(
+                "mrs {}, PRIMASK",
+                "mrs {}, FAULTMASK",
+                "cpsid i",
+                "cpsid f",
+                out(reg) primask,
+                out(reg) faultmask,
+                options(nomem, nostack, preserves_flags),
+            );
+        }
+
+        // Restores FAULTMASK and PRIMASK when dropped, so that they're restored even if `f`
+        // unwinds
+        struct Guard {
+            primask: u32,
+            faultmask: u32,
+        }
+
+        impl Drop for Guard {
+            #[inline]
+            fn drop(&mut self) {
+                // Ensure no preceeding memory accesses are reordered to after the masks are
+                // cleared.
+                compiler_fence(Ordering::SeqCst);
+
+                // Restore the masks in the reverse order in which they were set.
+                unsafe {
+                    if self.faultmask & 1 == 0 {
+                        asm!("cpsie f", options(nomem, nostack, preserves_flags));
+                    }
+                    restore(Primask::from_bits(self.primask));
+                }
+            }
+        }
+
+        let _guard = Guard { primask, faultmask };
+
+        // Ensure no subsequent memory accesses are reordered to before the masks are set.
+        compiler_fence(Ordering::SeqCst);
+
+        f()
+    }
+
+    #[cfg(any(armv6m, armv8m_base))]
+    free(f)
+}
+
+/// Execute closure `f` with all interrupts masked whose priority is the same as or lower than
+/// that of `interrupt`
+///
+/// Reads the current NVIC priority of `interrupt`, raises BASEPRI to it (BASEPRI is never
+/// lowered, see [`register::basepri_max`](crate::register::basepri_max)), runs `f` and restores
+/// the previous BASEPRI value. `interrupt` itself, and every interrupt or exception sharing its
+/// group priority or with a lower one, can't preempt `f`; higher priority ones still can.
+///
+/// Expressing the critical section relative to the interrupt whose data it protects avoids
+/// hard-coding priority values that must be kept in sync with the NVIC configuration.
+///
+/// *NOTE* If `interrupt` has priority value `0` (the highest priority), nothing is masked,
+/// because writing `0` to BASEPRI disables priority masking.
+#[cfg(all(cortex_m, not(armv6m), not(armv8m_base)))]
+#[inline]
+pub fn free_below<I, F, R>(interrupt: I, f: F) -> R
+where
+    I: InterruptNumber,
+    F: FnOnce() -> R,
+{
+    use crate::register::{basepri, basepri_max};
+
+    // Restores BASEPRI when dropped, so that it's restored even if `f` unwinds
+    struct Guard(u8);
+
+    impl Drop for Guard {
+        #[inline]
+        fn drop(&mut self) {
+            // Ensure no preceeding memory accesses are reordered to after BASEPRI is restored.
+            compiler_fence(Ordering::SeqCst);
+
+            unsafe { basepri::write(self.0) };
+        }
+    }
+
+    let _guard = Guard(basepri::read());
+    basepri_max::write(crate::peripheral::NVIC::get_priority(interrupt));
+
+    // Ensure no subsequent memory accesses are reordered to before BASEPRI is raised.
+    compiler_fence(Ordering::SeqCst);
+
+    f()
+}
+
+/// Compare-and-swap of the word at `ptr`, using the exclusive monitor (LDREX/STREX)
+///
+/// Loads the word with LDREX and, if it equals `current`, tries to store `new` with STREX.
+/// Returns `Ok` with the previous value if `new` was stored, otherwise `Err` with the value that
+/// was observed.
+///
+/// This makes a single attempt: the store also fails, returning `Err(current)`, if the exclusive
+/// monitor was cleared between the load and the store, e.g. because an exception was taken (which
+/// includes a context switch). Callers that need a full compare-and-swap must retry in a loop
+/// until the function returns `Ok`, or `Err` with a value other than `current`.
+///
+/// No memory barrier is issued, which is enough to synchronize with interrupt handlers on a
+/// single core; use [`asm::dmb`](crate::asm::dmb) to order the access with respect to other bus
+/// masters.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads and writes and 4-byte aligned, and must only be accessed
+/// atomically by other contexts while this function runs.
+#[cfg(all(cortex_m, not(armv6m)))]
+#[inline]
+pub unsafe fn ldrex_strex_cas(ptr: *mut u32, current: u32, new: u32) -> Result<u32, u32> {
+    let old: u32;
+    let failed: u32;
+    asm!(
+        "ldrex {old}, [{ptr}]",
+        "cmp {old}, {current}",
+        "bne 2f",
+        "strex {failed}, {new}, [{ptr}]",
+        "b 3f",
+        "2:",
+        "clrex",
+        "3:",
+        ptr = in(reg) ptr,
+        current = in(reg) current,
+        new = in(reg) new,
+        old = out(reg) old,
+        // `failed` keeps its initial value of 1 if the comparison fails
+        failed = inout(reg) 1u32 => failed,
+        options(nostack),
+    );
+
+    if failed == 0 {
+        Ok(old)
+    } else {
+        Err(old)
+    }
+}
+
+// Make a `free()` function available to allow checking dependencies without specifying a target,
+// but that will panic at runtime if executed.
+#[doc(hidden)]
+#[cfg(not(cortex_m))]
+#[inline]
+pub fn free<F, R>(_: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    panic!("cortex_m::interrupt::free() is only functional on cortex-m platforms");
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,84 @@
+//! Single value handoff between an interrupt handler and the main thread
+
+use core::cell::UnsafeCell;
+
+use critical_section::CriticalSection;
+
+/// A slot that moves a value from one execution context to another, e.g. from an interrupt
+/// handler to `main`
+///
+/// The producer [`store`](SpscCell::store)s a value from within a critical section, and the
+/// consumer [`take`](SpscCell::take)s it out. Unlike a `Mutex<RefCell<Option<T>>>`, the slot
+/// needs no borrow bookkeeping and can be placed in a `static` directly.
+///
+/// Both sides access the slot inside a critical section: `store` takes the token of the
+/// critical section it's called from, and `take` enters one itself (see the `critical-section`
+/// crate). `T` can be any type, so the value can't be moved with a single atomic instruction,
+/// not even on targets that have atomics; the critical section is only held while the value is
+/// moved in or out.
+///
+/// # Example
+///
+/// ``` no_run
+/// use cortex_m::interrupt::SpscCell;
+///
+/// static FRAME: SpscCell<[u8; 64]> = SpscCell::new();
+///
+/// // Interrupt handler: hand a received frame over to `main`
+/// fn on_rx() {
+///     let frame = [0u8; 64];
+///     critical_section::with(|cs| {
+///         FRAME.store(frame, &cs);
+///     });
+/// }
+///
+/// fn main() {
+///     loop {
+///         if let Some(frame) = FRAME.take() {
+///             // process `frame`
+///         }
+///     }
+/// }
+/// ```
+pub struct SpscCell<T> {
+    value: UnsafeCell<Option<T>>,
+}
+
+// NOTE(unsafe) the value is only accessed inside critical sections and only moved, never shared,
+// between contexts
+unsafe impl<T: Send> Sync for SpscCell<T> {}
+
+impl<T> SpscCell<T> {
+    /// Creates an empty cell
+    #[inline]
+    pub const fn new() -> Self {
+        SpscCell {
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Stores `value` in the cell
+    ///
+    /// Returns the previous value if the consumer hasn't taken it yet.
+    #[inline]
+    pub fn store(&self, value: T, _cs: &CriticalSection) -> Option<T> {
+        // NOTE(unsafe) we are in a critical section
+        unsafe { (*self.value.get()).replace(value) }
+    }
+
+    /// Takes the value out of the cell, leaving it empty
+    ///
+    /// Returns `None` if no value has been stored since the last call.
+    #[inline]
+    pub fn take(&self) -> Option<T> {
+        // NOTE(unsafe) we are in a critical section
+        critical_section::with(|_| unsafe { (*self.value.get()).take() })
+    }
+}
+
+impl<T> Default for SpscCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
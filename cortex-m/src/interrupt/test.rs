@@ -0,0 +1,57 @@
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::panic::catch_unwind;
+
+#[cfg(debug_assertions)]
+use crate::interrupt::FREE_DEPTH;
+use crate::interrupt::{free_primask, would_be_masked};
+use crate::register::primask::Primask;
+
+#[test]
+fn basepri_masking() {
+    // BASEPRI = 0 never masks
+    assert!(!would_be_masked(0, 0xff, 8));
+    assert!(!would_be_masked(0, 0, 8));
+
+    assert!(would_be_masked(0x40, 0x40, 8));
+    assert!(would_be_masked(0x40, 0x80, 8));
+    assert!(!would_be_masked(0x40, 0x3f, 8));
+
+    // with 3 implemented bits only the top 3 bits are compared
+    assert!(would_be_masked(0x40, 0x5f, 3));
+    assert!(!would_be_masked(0x40, 0x3f, 3));
+    // a BASEPRI value with only unimplemented bits set reads back as 0
+    assert!(!would_be_masked(0x1f, 0xff, 3));
+}
+
+// Emulated PRIMASK used by `free_primask` on the host; `true` means interrupts are disabled
+static PRIMASK: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn disable_and_save() -> Primask {
+    Primask::from_bits(PRIMASK.swap(true, Ordering::Relaxed) as u32)
+}
+
+pub(super) unsafe fn restore(primask: Primask) {
+    if primask.is_active() {
+        PRIMASK.store(false, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn free_restores_primask_on_panic() {
+    let result = catch_unwind(|| free_primask(|| panic!("inside free")));
+    assert!(result.is_err());
+    assert!(!PRIMASK.load(Ordering::Relaxed));
+
+    // a nested critical section that unwinds keeps the outer one's interrupts disabled
+    free_primask(|| {
+        let result = catch_unwind(|| free_primask(|| panic!("inside nested free")));
+        assert!(result.is_err());
+        assert!(PRIMASK.load(Ordering::Relaxed));
+    });
+    assert!(!PRIMASK.load(Ordering::Relaxed));
+
+    #[cfg(debug_assertions)]
+    assert_eq!(FREE_DEPTH.load(Ordering::Relaxed), 0);
+}
@@ -5,6 +5,7 @@
 use core::{fmt, ptr, slice};
 
 use crate::peripheral::itm::Stim;
+use crate::peripheral::ITM;
 
 // NOTE assumes that `bytes` is 32-bit aligned
 unsafe fn write_words(stim: &mut Stim, bytes: &[u32]) {
@@ -55,7 +56,35 @@ unsafe fn write_aligned_impl(port: &mut Stim, buffer: &[u8]) {
     }
 }
 
-struct Port<'p>(&'p mut Stim);
+/// A stimulus port that implements [`fmt::Write`].
+///
+/// This lets an ITM port be used with `write!` and other `core::fmt` based APIs, not just the
+/// `iprint!` macros. Writes block until the port has accepted all the data.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cortex_m::{itm::Port, peripheral::Peripherals};
+/// use core::fmt::Write;
+///
+/// let mut p = Peripherals::take().unwrap();
+/// let mut port = Port::new(&mut p.ITM, 0);
+///
+/// write!(port, "x = {}", 42).ok();
+/// ```
+pub struct Port<'p>(&'p mut Stim);
+
+impl<'p> Port<'p> {
+    /// Wraps stimulus port number `port` of the `itm`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in the range `0..256`.
+    #[inline]
+    pub fn new(itm: &'p mut ITM, port: usize) -> Self {
+        Port(&mut itm.stim[port])
+    }
+}
 
 impl<'p> fmt::Write for Port<'p> {
     #[inline]
@@ -65,6 +94,95 @@ impl<'p> fmt::Write for Port<'p> {
     }
 }
 
+/// A buffered writer for an ITM stimulus port
+///
+/// Bytes written to the buffer are only sent to the port when it is full or when [`flush`] is
+/// called, as 4-byte aligned data (see [`write_aligned`]). Batching many small writes this way
+/// sends mostly 32-bit stimulus writes and amortizes the FIFO-ready polling. It implements
+/// [`fmt::Write`], so `write!` can be used to fill it.
+///
+/// The buffer is flushed when dropped. `N` must not be `0`; this is checked at compile time.
+///
+/// **NOTE** Like [`write_all`], flushing can be preempted between two stimulus writes. Messages
+/// written by interrupt handlers to the same port can thus end up in the middle of a flushed
+/// buffer, but never in the middle of a word.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cortex_m::{itm::FrameBuffer, peripheral::ITM};
+/// use core::fmt::Write;
+///
+/// # let port = unsafe { &mut (*ITM::PTR).stim[0] };
+/// let mut buffer = FrameBuffer::<64>::new(port);
+/// for i in 0..10 {
+///     write!(buffer, "{} ", i).ok();
+/// }
+/// buffer.flush();
+/// ```
+///
+/// [`flush`]: FrameBuffer::flush
+pub struct FrameBuffer<'p, const N: usize> {
+    port: &'p mut Stim,
+    buffer: Aligned<[u8; N]>,
+    len: usize,
+}
+
+impl<'p, const N: usize> FrameBuffer<'p, N> {
+    // `write` would never make progress with an empty buffer
+    const NONZERO: () = assert!(N > 0, "FrameBuffer size must not be 0");
+
+    /// Creates an empty buffer writing to `port`
+    #[inline]
+    pub fn new(port: &'p mut Stim) -> Self {
+        let () = Self::NONZERO;
+
+        FrameBuffer {
+            port,
+            buffer: Aligned([0; N]),
+            len: 0,
+        }
+    }
+
+    /// Appends `bytes` to the buffer, flushing it every time it is full
+    #[inline]
+    pub fn write(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            if self.len == N {
+                self.flush();
+            }
+
+            let n = bytes.len().min(N - self.len);
+            self.buffer.0[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+            self.len += n;
+            bytes = &bytes[n..];
+        }
+    }
+
+    /// Sends the buffered bytes to the port and empties the buffer
+    #[inline]
+    pub fn flush(&mut self) {
+        // NOTE(unsafe) the buffer is 4-byte aligned
+        unsafe { write_aligned_impl(self.port, &self.buffer.0[..self.len]) };
+        self.len = 0;
+    }
+}
+
+impl<'p, const N: usize> fmt::Write for FrameBuffer<'p, N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<'p, const N: usize> Drop for FrameBuffer<'p, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 /// A wrapper type that aligns its contents on a 4-Byte boundary.
 ///
 /// ITM transfers are most efficient when the data is 4-Byte-aligned. This type provides an easy
@@ -122,6 +240,72 @@ pub fn write_all(port: &mut Stim, buffer: &[u8]) {
     }
 }
 
+/// Writes `buffer` to an ITM port inside a critical section.
+///
+/// Unlike [`write_all`], which can be preempted between two stimulus writes, this disables
+/// interrupts for the whole transfer (see [`interrupt::free`](crate::interrupt::free)), so the
+/// bytes of `buffer` are never interleaved with data written to the same `port` by an interrupt
+/// handler. Use it to emit complete frames for frame-based trace decoders.
+///
+/// **NOTE** Interrupts stay masked until the ITM FIFO has accepted the whole buffer, which can
+/// take a long time for large buffers or slow SWO clocks. Prefer `write_all` when only byte-level
+/// atomicity is required.
+#[inline]
+pub fn write_all_atomic(port: &mut Stim, buffer: &[u8]) {
+    crate::interrupt::free(|| write_all(port, buffer))
+}
+
+/// Writes a binary event frame to an ITM port
+///
+/// This is a cheap alternative to `iprint!` for hot paths: instead of formatting a message on the
+/// device, only an event identifier and some raw words are sent, and the host formats them.
+///
+/// The frame consists of 32-bit stimulus writes only:
+///
+/// | Word | Contents                                                        |
+/// |------|-----------------------------------------------------------------|
+/// | 0    | header: `id` in bits 0..16, number of payload words in bits 16..32 |
+/// | 1..  | `payload`, one word per write                                   |
+///
+/// Each word arrives at the host as a 4-byte ITM software source packet (little endian) from the
+/// stimulus port used. A host decoder reads the header, then the number of words it announces.
+///
+/// **NOTE** Like [`write_all`], this can be preempted between two words. If interrupt handlers log
+/// events to the same port, call it from a critical section (see
+/// [`interrupt::free`](crate::interrupt::free)) to keep frames intact.
+///
+/// # Panics
+///
+/// Panics if `payload` has more than 65535 words.
+#[inline]
+pub fn write_event(port: &mut Stim, id: u16, payload: &[u32]) {
+    assert!(payload.len() <= usize::from(u16::MAX));
+
+    while !port.is_fifo_ready() {}
+    port.write_u32(u32::from(id) | (payload.len() as u32) << 16);
+
+    for word in payload {
+        while !port.is_fifo_ready() {}
+        port.write_u32(*word);
+    }
+}
+
+/// Returns the address of stimulus port number `port`
+///
+/// This is meant to let a DMA controller write directly into a stimulus port, e.g. to stream a
+/// log buffer without CPU involvement.
+///
+/// **NOTE** Writes through this pointer bypass the FIFO-ready handshake done by the other
+/// functions of this module: a write while the port FIFO is full is silently dropped. Flow
+/// control must be handled another way, e.g. by pacing the DMA transfers. Writes also race with
+/// any other writer to the same port, so a port used for DMA should not be used for anything
+/// else.
+#[inline]
+pub fn stim_ptr(port: u8) -> *mut u32 {
+    // NOTE(unsafe) only computes an address, nothing is read
+    unsafe { ptr::addr_of!((*ITM::PTR).stim[usize::from(port)]) as *mut u32 }
+}
+
 /// Writes a 4-byte aligned `buffer` to an ITM port.
 ///
 /// # Examples
@@ -156,3 +340,78 @@ pub fn write_fmt(port: &mut Stim, args: fmt::Arguments) {
 pub fn write_str(port: &mut Stim, string: &str) {
     write_all(port, string.as_bytes())
 }
+
+/// Writes `value` to the ITM `port` as 8 hexadecimal digits, e.g. `0000002a`
+///
+/// Unlike `iprint!("{:08x}", value)`, this doesn't use `core::fmt`, whose machinery can add
+/// kilobytes to a binary. Firmware that only ever logs numbers can save that space by using this
+/// and [`write_dec_u32`] instead of the formatting macros.
+#[inline]
+pub fn write_hex_u32(port: &mut Stim, value: u32) {
+    let mut buffer = [0; 8];
+    for (i, digit) in buffer.iter_mut().rev().enumerate() {
+        *digit = b"0123456789abcdef"[((value >> (4 * i)) & 0xf) as usize];
+    }
+    write_all(port, &buffer);
+}
+
+/// Writes `value` to the ITM `port` in decimal, without leading zeros
+///
+/// See [`write_hex_u32`] for why to use this instead of `iprint!("{}", value)`.
+#[inline]
+pub fn write_dec_u32(port: &mut Stim, mut value: u32) {
+    // u32::MAX has 10 digits
+    let mut buffer = [0; 10];
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    write_all(port, &buffer[start..]);
+}
+
+/// Writes a hexdump of `len` bytes starting at `addr` to the ITM `port`
+///
+/// Each line shows the address of its first byte, up to 16 bytes in hexadecimal and the same
+/// bytes as ASCII, with non-printable bytes shown as `.`:
+///
+/// ``` text
+/// 20000000: 48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a 00 ff  |Hello, world!...|
+/// ```
+///
+/// The output is formatted on the fly; no buffer is allocated.
+///
+/// # Safety
+///
+/// `addr` must be valid for reads of `len` bytes.
+#[allow(clippy::missing_inline_in_public_items)]
+pub unsafe fn hexdump(port: &mut Stim, addr: *const u8, len: usize) {
+    use core::fmt::Write;
+
+    let mut port = Port(port);
+    let bytes = slice::from_raw_parts(addr, len);
+
+    for (i, line) in bytes.chunks(16).enumerate() {
+        write!(port, "{:08x}:", addr as usize + i * 16).ok();
+        for byte in line {
+            write!(port, " {:02x}", byte).ok();
+        }
+        for _ in line.len()..16 {
+            port.write_str("   ").ok();
+        }
+        port.write_str("  |").ok();
+        for &byte in line {
+            let c = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            port.write_char(c).ok();
+        }
+        port.write_str("|\n").ok();
+    }
+}
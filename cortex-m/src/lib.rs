@@ -19,6 +19,26 @@
 //! or critical sections are managed as part of an RTOS. In these cases, you should use
 //! a target-specific implementation instead, typically provided by a HAL or RTOS crate.
 //!
+//! ## `basepri-critical-section`
+//!
+//! On ARMv7-M and ARMv8-M Mainline, this feature makes [`interrupt::free`] raise BASEPRI instead
+//! of disabling all interrupts with PRIMASK. The BASEPRI value must be set once in the final
+//! binary with the [`critical_section_basepri!`] macro. Interrupts more urgent than that level
+//! keep running inside critical sections, which bounds their latency, but they must not access
+//! any data protected by `interrupt::free` (including the emulated atomics in
+//! [`interrupt::atomic`]). Entering and leaving the critical section also takes a few more
+//! instructions than with PRIMASK.
+//!
+//! On other architectures, and when the feature is disabled (the default), `interrupt::free`
+//! disables all interrupts. The `critical-section-single-core` implementation always uses PRIMASK.
+//!
+//! ## `itm-enabled`
+//!
+//! This feature is enabled by default. When it's disabled, the [`iprint!`] and [`iprintln!`]
+//! macros compile to nothing: their arguments are still type checked, but no ITM output is
+//! generated. This turns off all ITM logging, e.g. for release firmware, without `#[cfg]`
+//! attributes at every call site. The functions of the [`itm`] module are not affected.
+//!
 //! ## `cm7-r0p1`
 //!
 //! This feature enables workarounds for errata found on Cortex-M7 chips with revision r0p1. Some
@@ -65,12 +85,35 @@ pub mod interrupt;
 pub mod itm;
 pub mod peripheral;
 pub mod register;
+pub mod timer;
+#[cfg(all(not(armv6m), not(armv8m_base)))]
+pub mod trace;
 
 pub use crate::peripheral::Peripherals;
 
 #[cfg(all(cortex_m, feature = "critical-section-single-core"))]
 mod critical_section;
 
+/// `true` if the target has the BASEPRI and FAULTMASK registers (ARMv7-M and ARMv8-M Mainline)
+///
+/// These constants are computed from the target the crate is compiled for, and can be used in
+/// `const` context or in a plain `if` to select an implementation without repeating the crate's
+/// `cfg` logic. They are all `false` when not compiling for a Cortex-M target.
+pub const HAS_BASEPRI: bool = cfg!(all(cortex_m, not(armv6m), not(armv8m_base)));
+
+/// `true` if the target has a floating-point unit (`eabihf` targets)
+pub const HAS_FPU: bool = cfg!(all(cortex_m, has_fpu));
+
+/// `true` if the cache maintenance operations of [`SCB`](crate::peripheral::SCB) are available
+/// (every architecture but ARMv6-M)
+///
+/// *NOTE* Caches are optional: even when this is `true`, the device may not implement them.
+pub const HAS_CACHE: bool = cfg!(all(cortex_m, not(armv6m)));
+
+/// `true` if the target has the MSPLIM and PSPLIM stack limit registers (ARMv8-M Mainline), see
+/// `register::set_stack_limit`
+pub const HAS_STACK_LIMIT: bool = cfg!(all(cortex_m, armv8m_main));
+
 /// Used to reexport items for use in macros. Do not use directly.
 /// Not covered by semver guarantees.
 #[doc(hidden)]
@@ -1,4 +1,5 @@
 /// Macro for sending a formatted string through an ITM channel
+#[cfg(feature = "itm-enabled")]
 #[macro_export]
 macro_rules! iprint {
     ($channel:expr, $s:expr) => {
@@ -9,7 +10,28 @@ macro_rules! iprint {
     };
 }
 
+/// Macro for sending a formatted string through an ITM channel
+///
+/// The `itm-enabled` feature is disabled: this only type checks its arguments.
+#[cfg(not(feature = "itm-enabled"))]
+#[macro_export]
+macro_rules! iprint {
+    // NOTE the arguments are used exactly as in the enabled macro, so the same code compiles with
+    // and without the feature, but the branch is never taken
+    ($channel:expr, $s:expr) => {
+        if false {
+            $crate::itm::write_str($channel, $s);
+        }
+    };
+    ($channel:expr, $($arg:tt)*) => {
+        if false {
+            $crate::itm::write_fmt($channel, format_args!($($arg)*));
+        }
+    };
+}
+
 /// Macro for sending a formatted string through an ITM channel, with a newline.
+#[cfg(feature = "itm-enabled")]
 #[macro_export]
 macro_rules! iprintln {
     ($channel:expr) => {
@@ -23,6 +45,30 @@ macro_rules! iprintln {
     };
 }
 
+/// Macro for sending a formatted string through an ITM channel, with a newline.
+///
+/// The `itm-enabled` feature is disabled: this only type checks its arguments.
+#[cfg(not(feature = "itm-enabled"))]
+#[macro_export]
+macro_rules! iprintln {
+    // NOTE see `iprint!`
+    ($channel:expr) => {
+        if false {
+            $crate::itm::write_str($channel, "\n");
+        }
+    };
+    ($channel:expr, $fmt:expr) => {
+        if false {
+            $crate::itm::write_str($channel, concat!($fmt, "\n"));
+        }
+    };
+    ($channel:expr, $fmt:expr, $($arg:tt)*) => {
+        if false {
+            $crate::itm::write_fmt($channel, format_args!(concat!($fmt, "\n"), $($arg)*));
+        }
+    };
+}
+
 /// Macro to create a mutable reference to a statically allocated value
 ///
 /// This macro returns a value with type `Option<&'static mut $ty>`. `Some($expr)` will be returned
@@ -93,6 +139,164 @@ macro_rules! singleton {
     };
 }
 
+/// Macro to set the BASEPRI value used by `interrupt::free` with the `basepri-critical-section`
+/// feature
+///
+/// `$prio` is a hardware priority value, like the ones passed to
+/// [`NVIC::set_priority`](crate::peripheral::NVIC::set_priority): interrupts and exceptions with
+/// this priority value or a larger one (i.e. the same or lower urgency) are masked inside
+/// critical sections, while more urgent ones keep running. It must not be `0`, which would
+/// disable masking; this is checked at compile time.
+///
+/// This macro must be invoked exactly once in the final binary when the
+/// `basepri-critical-section` feature is enabled on ARMv7-M or ARMv8-M Mainline; otherwise
+/// linking fails with an undefined `__cortex_m_critical_section_basepri` symbol.
+///
+/// # Example
+///
+/// ``` no_run
+/// // Interrupts with priority 0x00..=0x1f are never masked by `interrupt::free`
+/// cortex_m::critical_section_basepri!(0x20);
+/// ```
+#[macro_export]
+macro_rules! critical_section_basepri {
+    ($prio:expr) => {
+        #[export_name = "__cortex_m_critical_section_basepri"]
+        static __CORTEX_M_CRITICAL_SECTION_BASEPRI: u8 = $prio;
+
+        const _: () = assert!(
+            $prio != 0,
+            "critical_section_basepri!(0) would disable masking in critical sections"
+        );
+    };
+}
+
+/// Macro to define the `PendSV` exception handler as a context switch trampoline
+///
+/// This is the building block of preemptive schedulers: the generated handler saves the
+/// callee-saved registers R4-R11 of the running task on its process stack, calls
+/// `$switch(old_psp)` to pick the next task and then restores R4-R11 of that task from the
+/// process stack pointer returned by `$switch` before returning to it. The handler is written in
+/// assembly (it's not possible to do this correctly from a Rust function body, as the compiler
+/// is free to use R4-R11 itself), so no `#[naked]` functions are required.
+///
+/// `$switch` must be a `fn(*mut u32) -> *mut u32`. Its argument is the process stack pointer of
+/// the task being switched out, *after* R4-R11 were pushed to it, and it must return the process
+/// stack pointer of the task to switch to, in the same format. The stack layout, from the stack
+/// pointer upwards, is:
+///
+/// | Offset (words) | Register                         |
+/// |----------------|----------------------------------|
+/// | 0 - 7          | R4, R5, R6, R7, R8, R9, R10, R11 |
+/// | 8 - 15         | R0, R1, R2, R3, R12, LR, PC, xPSR (stacked by the hardware) |
+///
+/// To start a new task, `$switch` can return a pointer to such a frame crafted by hand, with PC
+/// set to the task entry point and xPSR set to `0x0100_0000` (Thumb bit).
+///
+/// # Notes
+///
+/// - Threads must run on the process stack (CONTROL.SPSEL = 1) for this handler to work.
+/// - PendSV should be configured with the lowest priority, so that it never preempts another
+///   exception handler.
+/// - This macro defines the `PendSV` symbol; it can't be combined with an
+///   `#[exception] fn PendSV()` handler.
+///
+/// # Example
+///
+/// ``` no_run
+/// static mut NEXT_PSP: *mut u32 = core::ptr::null_mut();
+///
+/// fn switch(old_psp: *mut u32) -> *mut u32 {
+///     unsafe { core::mem::replace(&mut NEXT_PSP, old_psp) }
+/// }
+///
+/// cortex_m::pendsv_context_switch!(switch);
+/// ```
+#[macro_export]
+macro_rules! pendsv_context_switch {
+    ($switch:path) => {
+        #[doc(hidden)]
+        #[export_name = "__cortex_m_context_switch"]
+        pub unsafe extern "C" fn __cortex_m_context_switch(psp: *mut u32) -> *mut u32 {
+            let switch: fn(*mut u32) -> *mut u32 = $switch;
+            switch(psp)
+        }
+
+        #[cfg(target_arch = "arm")]
+        ::core::arch::global_asm!(
+            ".section .text.PendSV, \"ax\"
+             .global PendSV
+             .type PendSV,%function
+             .thumb_func
+             PendSV:
+             ldr r0, =__cortex_m_pendsv_trampoline
+             bx r0
+             .ltorg"
+        );
+    };
+}
+
+/// Macro to define the `PendSV` exception handler as an FPU-aware context switch trampoline
+///
+/// This works like [`pendsv_context_switch!`] but additionally preserves the floating-point
+/// state of tasks that use the FPU. On exception entry the hardware stacks S0-S15 and FPSCR
+/// (lazily, if enabled) for tasks with an active floating-point context; this handler saves the
+/// remaining callee-saved registers S16-S31 of such tasks, as indicated by the FType bit of the
+/// EXC_RETURN value, and restores them for the next task if its EXC_RETURN value says so.
+///
+/// `$switch` must be a `fn(*mut u32) -> *mut u32`, with the same contract as for
+/// `pendsv_context_switch!`. The stack layout, from the stack pointer upwards, is:
+///
+/// | Offset (words) | Contents                                               |
+/// |----------------|--------------------------------------------------------|
+/// | 0 - 7          | R4, R5, R6, R7, R8, R9, R10, R11                       |
+/// | 8              | EXC_RETURN                                             |
+/// | 9 - 24         | S16, ..., S31 (only present if EXC_RETURN bit 4 is 0) |
+/// | ...            | Frame stacked by the hardware (basic or extended)      |
+///
+/// A hand-crafted frame for a new task should use an EXC_RETURN value of `0xFFFF_FFFD` (return
+/// to Thread mode using the process stack, no floating-point context) and a basic hardware frame.
+///
+/// This macro is only available on targets with an FPU (`thumbv7em-none-eabihf` and
+/// `thumbv8m.main-none-eabihf`); using it on other targets is a compile time error.
+///
+/// # Example
+///
+/// ``` no_run
+/// static mut NEXT_PSP: *mut u32 = core::ptr::null_mut();
+///
+/// fn switch(old_psp: *mut u32) -> *mut u32 {
+///     unsafe { core::mem::replace(&mut NEXT_PSP, old_psp) }
+/// }
+///
+/// cortex_m::pendsv_context_switch_fpu!(switch);
+/// ```
+#[macro_export]
+macro_rules! pendsv_context_switch_fpu {
+    ($switch:path) => {
+        const _: () = $crate::asm::__HAS_FPU;
+
+        #[doc(hidden)]
+        #[export_name = "__cortex_m_context_switch"]
+        pub unsafe extern "C" fn __cortex_m_context_switch(psp: *mut u32) -> *mut u32 {
+            let switch: fn(*mut u32) -> *mut u32 = $switch;
+            switch(psp)
+        }
+
+        #[cfg(target_arch = "arm")]
+        ::core::arch::global_asm!(
+            ".section .text.PendSV, \"ax\"
+             .global PendSV
+             .type PendSV,%function
+             .thumb_func
+             PendSV:
+             ldr r0, =__cortex_m_pendsv_trampoline_fpu
+             bx r0
+             .ltorg"
+        );
+    };
+}
+
 /// ``` compile_fail
 /// use cortex_m::singleton;
 ///
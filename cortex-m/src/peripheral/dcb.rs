@@ -48,6 +48,14 @@ impl DCB {
     }
 
     /// Enables the [`DebugMonitor`](crate::peripheral::scb::Exception::DebugMonitor) exception
+    ///
+    /// This allows handling breakpoints and DWT watchpoints in firmware, without a halting debugger.
+    /// Set the priority of the exception with
+    /// [`SCB::set_priority`](crate::peripheral::SCB::set_priority) and
+    /// [`SystemHandler::DebugMonitor`](crate::peripheral::scb::SystemHandler::DebugMonitor).
+    ///
+    /// *NOTE* MON_EN is ignored while halting debug is enabled (DHCSR.C_DEBUGEN set), i.e. while a
+    /// debugger controls the core: debug events halt the core instead.
     #[inline]
     pub fn enable_debug_monitor(&mut self) {
         unsafe {
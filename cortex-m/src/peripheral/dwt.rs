@@ -57,6 +57,7 @@ bitfield! {
     #[derive(Copy, Clone)]
     pub struct Ctrl(u32);
     cyccntena, set_cyccntena: 0;
+    u8, synctap, set_synctap: 11, 10;
     pcsamplena, set_pcsamplena: 12;
     exctrcena, set_exctrcena: 16;
     noprfcnt, _: 24;
@@ -66,6 +67,20 @@ bitfield! {
     u8, numcomp, _: 31, 28;
 }
 
+/// Tap of the synchronization packet counter, see [`DWT::set_sync_count_tap`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[repr(u8)]
+pub enum SyncTap {
+    /// Synchronization packet counter disabled
+    Disabled = 0b00,
+    /// Tap at CYCCNT bit 24
+    Bit24 = 0b01,
+    /// Tap at CYCCNT bit 26
+    Bit26 = 0b10,
+    /// Tap at CYCCNT bit 28
+    Bit28 = 0b11,
+}
+
 /// Comparator
 #[repr(C)]
 pub struct Comparator {
@@ -105,6 +120,37 @@ impl DWT {
         self.ctrl.read().numcomp()
     }
 
+    /// Configures comparator `n` as a data watchpoint
+    ///
+    /// A watchpoint debug event is generated when an address matching `address` is accessed as
+    /// selected by `access_type`. The `mask` lowest bits of the address are ignored, so that a
+    /// whole (naturally aligned) range of `2^mask` bytes is watched. The event halts the core if
+    /// a debugger is attached, or fires the `DebugMonitor` exception when monitor debug is
+    /// enabled (see [`DCB`](crate::peripheral::DCB) and `DEMCR.MON_EN`), which allows on-device
+    /// watchpoints without a debugger.
+    ///
+    /// Returns [`DwtError::InvalidComparator`] if comparator `n` is not implemented.
+    #[cfg(not(armv6m))]
+    #[inline]
+    pub fn set_data_watchpoint(
+        &mut self,
+        n: usize,
+        address: u32,
+        mask: u32,
+        access_type: AccessType,
+    ) -> Result<(), DwtError> {
+        if n >= usize::from(self.num_comp()) {
+            return Err(DwtError::InvalidComparator);
+        }
+
+        self.c[n].configure(ComparatorFunction::Address(ComparatorAddressSettings {
+            address,
+            mask,
+            emit: EmitOption::WatchpointDebugEvent,
+            access_type,
+        }))
+    }
+
     /// Returns `true` if the the implementation supports sampling and exception tracing
     #[cfg(not(armv6m))]
     #[inline]
@@ -198,6 +244,22 @@ impl DWT {
         }
     }
 
+    /// Selects the CYCCNT bit that drives the synchronization packet counter
+    ///
+    /// Synchronization packets are emitted every time the selected bit of the cycle counter
+    /// toggles, which allows a trace decoder to recover the packet framing. The cycle counter
+    /// must be enabled for synchronization packets to be generated.
+    #[cfg(not(armv6m))]
+    #[inline]
+    pub fn set_sync_count_tap(&mut self, tap: SyncTap) {
+        unsafe {
+            self.ctrl.modify(|mut r| {
+                r.set_synctap(tap as u8);
+                r
+            });
+        }
+    }
+
     /// Whether to periodically generate PC samples
     #[cfg(not(armv6m))]
     #[inline]
@@ -229,6 +291,42 @@ impl DWT {
         unsafe { (*Self::PTR).cyccnt.read() }
     }
 
+    /// Busy-waits for at least `cycles` CPU cycles, using the cycle counter
+    ///
+    /// Unlike [`asm::delay`](crate::asm::delay), the duration doesn't depend on instruction
+    /// timing or the optimization level: this spins until `CYCCNT` has advanced by `cycles`. The
+    /// 32-bit wraparound of the counter is handled, so any `cycles` value is valid.
+    ///
+    /// *NOTE* The cycle counter must be enabled, see [`DWT::enable_cycle_counter`], or this never
+    /// returns. On ARMv6-M, which doesn't have a cycle counter, this falls back to
+    /// `asm::delay`.
+    #[inline]
+    pub fn delay_cycles(cycles: u32) {
+        #[cfg(not(armv6m))]
+        {
+            let start = Self::cycle_count();
+            while Self::cycle_count().wrapping_sub(start) < cycles {}
+        }
+
+        #[cfg(armv6m)]
+        crate::asm::delay(cycles)
+    }
+
+    /// Returns a sample of the program counter, read from the Program Counter Sample Register
+    ///
+    /// Each read returns the address of a recently executed instruction, which allows
+    /// non-intrusive statistical profiling, e.g. by periodically logging the samples over ITM.
+    /// The architecture returns `0xFFFF_FFFF` when no valid sample is available, for example
+    /// while the core is halted in Debug state.
+    ///
+    /// *NOTE* PCSR is optional; on implementations without it, it reads as zero.
+    #[cfg(not(armv6m))]
+    #[inline]
+    pub fn pc_sample() -> u32 {
+        // NOTE(unsafe) atomic read with no side effects
+        unsafe { (*Self::PTR).pcsr.read() }
+    }
+
     /// Set the cycle count
     #[cfg(not(armv6m))]
     #[inline]
@@ -414,6 +512,8 @@ pub enum ComparatorFunction {
 pub enum DwtError {
     /// Invalid combination of [AccessType] and [EmitOption].
     InvalidFunction,
+    /// The requested comparator is not implemented, see [`DWT::num_comp`].
+    InvalidComparator,
 }
 
 impl Comparator {
@@ -4,6 +4,9 @@
 use volatile_register::RO;
 use volatile_register::RW;
 
+#[cfg(any(armv7m, armv8m, native))]
+use super::ICB;
+
 /// Register block
 #[repr(C)]
 pub struct RegisterBlock {
@@ -30,3 +33,20 @@ pub struct RegisterBlock {
     #[cfg(armv8m)]
     pub cppwr: RW<u32>,
 }
+
+#[cfg(any(armv7m, armv8m, native))]
+impl ICB {
+    /// Returns the number of interrupt lines supported by the NVIC
+    ///
+    /// This is `(ICTR.INTLINESNUM + 1) * 32`, read from the Interrupt Controller Type Register at
+    /// address `0xE000_E004` in the System Control Space (outside of the SCB). The value is
+    /// rounded up to a multiple of 32: the device may implement fewer interrupts, but no more.
+    /// Divide it by 32 to get the number of implemented ISER/ICER/ISPR/ICPR words.
+    #[inline]
+    pub fn interrupt_lines() -> u32 {
+        // NOTE(unsafe) atomic read with no side effects
+        let ictr = unsafe { (*Self::PTR).ictr.read() };
+
+        ((ictr & 0xf) + 1) * 32
+    }
+}
@@ -4,6 +4,7 @@
 
 use core::cell::UnsafeCell;
 use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 use volatile_register::{RO, RW, WO};
 
@@ -175,7 +176,72 @@ pub struct ITMSettings {
     pub timestamp_clk_src: TimestampClkSrc,
 }
 
+/// Complete ITM configuration applied by [`ITM::init_once`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ITMConfig {
+    /// Trace control settings, see [`ITM::configure`].
+    pub settings: ITMSettings,
+    /// Whether to emit synchronization packets (TCR.SYNCENA).
+    ///
+    /// These are generated by the DWT synchronization counter, see
+    /// [`DWT::set_sync_count_tap`](crate::peripheral::DWT::set_sync_count_tap).
+    pub sync: bool,
+    /// Enabled stimulus ports: bit `n` of `enabled_ports[block]` enables port `32 * block + n`,
+    /// see [`ITM::enable_ports`].
+    pub enabled_ports: [u32; 8],
+    /// Groups of 8 stimulus ports that unprivileged code can write to, see
+    /// [`ITM::set_unprivileged_ports`].
+    pub unprivileged_ports: u32,
+}
+
+/// Set once the ITM has been configured by [`ITM::init_once`]
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
 impl ITM {
+    /// Configures the ITM, unless this function has already been called
+    ///
+    /// Unlocks the ITM and applies `config` to the Trace Control, Trace Enable and Trace Privilege
+    /// registers, then returns `true`. All later calls do nothing and return `false`. The check
+    /// and the configuration happen in the same critical section, so independent modules can each
+    /// call this to make sure the ITM is configured, without coordinating which of them does it;
+    /// the first caller's `config` wins, and no caller sees `false` before the ITM is configured.
+    ///
+    /// This doesn't require an `ITM` instance, since the ITM is configured at most once. Other
+    /// code owning the `ITM` can still change the configuration afterwards, but must not write
+    /// to the ITM while this function may be running, e.g. from a lower priority context.
+    ///
+    /// *NOTE* Trace must be enabled in DEMCR (see
+    /// [`DCB::enable_trace`](crate::peripheral::DCB::enable_trace)) before calling this function,
+    /// otherwise the ITM ignores the writes.
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn init_once(config: ITMConfig) -> bool {
+        crate::interrupt::free(|| {
+            // NOTE a load followed by a store, as ARMv6-M has no atomic read-modify-write;
+            // interrupts are disabled here
+            if INITIALIZED.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            // NOTE the ITM is configured at most once, and the owner of `Peripherals.ITM` must
+            // not write to it concurrently, see above
+            let mut itm = ITM {
+                _marker: core::marker::PhantomData,
+            };
+            itm.unlock();
+            itm.configure(config.settings);
+            itm.enable_sync_packets(config.sync);
+            for (block, &mask) in config.enabled_ports.iter().enumerate() {
+                unsafe { itm.ter[block].write(mask) }
+            }
+            itm.set_unprivileged_ports(config.unprivileged_ports);
+
+            // only set once configured, so that a preempting caller never returns `false` early
+            INITIALIZED.store(true, Ordering::Relaxed);
+
+            true
+        })
+    }
+
     /// Removes the software lock on the ITM.
     #[inline]
     pub fn unlock(&mut self) {
@@ -189,20 +255,9 @@ impl ITM {
         unsafe {
             self.tcr.modify(|mut r| {
                 r.set_itmena(settings.enable);
-                r.set_tsena(settings.local_timestamps != LocalTimestampOptions::Disabled);
                 r.set_txena(settings.forward_dwt);
-                r.set_tsprescale(match settings.local_timestamps {
-                    LocalTimestampOptions::Disabled | LocalTimestampOptions::Enabled => 0b00,
-                    LocalTimestampOptions::EnabledDiv4 => 0b10,
-                    LocalTimestampOptions::EnabledDiv16 => 0b10,
-                    LocalTimestampOptions::EnabledDiv64 => 0b11,
-                });
-                r.set_gtsfreq(match settings.global_timestamps {
-                    GlobalTimestampOptions::Disabled => 0b00,
-                    GlobalTimestampOptions::Every128Cycles => 0b01,
-                    GlobalTimestampOptions::Every8192Cycles => 0b10,
-                    GlobalTimestampOptions::EveryPacket => 0b11,
-                });
+                set_local_timestamps(&mut r, settings.local_timestamps);
+                set_global_timestamps(&mut r, settings.global_timestamps);
                 r.set_swoena(match settings.timestamp_clk_src {
                     TimestampClkSrc::SystemClock => false,
                     TimestampClkSrc::AsyncTPIU => true,
@@ -213,4 +268,138 @@ impl ITM {
             });
         }
     }
+
+    /// Enables the ITM (sets TCR.ITMENA)
+    ///
+    /// Writes to the stimulus ports are ignored while the ITM is disabled, and their FIFO never
+    /// becomes ready, so [`iprint!`](crate::iprint) spins forever. The stimulus ports must also be
+    /// enabled, see [`ITM::enable_ports`].
+    ///
+    /// *NOTE* The ITM registers can only be written once trace is enabled in DEMCR (see
+    /// [`DCB::enable_trace`](crate::peripheral::DCB::enable_trace)), and, on some devices, after
+    /// [`ITM::unlock`].
+    #[inline]
+    pub fn enable(&mut self) {
+        unsafe {
+            self.tcr.modify(|mut r| {
+                r.set_itmena(true);
+                r
+            });
+        }
+    }
+
+    /// Disables the ITM (clears TCR.ITMENA)
+    #[inline]
+    pub fn disable(&mut self) {
+        unsafe {
+            self.tcr.modify(|mut r| {
+                r.set_itmena(false);
+                r
+            });
+        }
+    }
+
+    /// Whether to emit synchronization packets (TCR.SYNCENA)
+    ///
+    /// The packets are generated by the DWT synchronization counter, see
+    /// [`DWT::set_sync_count_tap`](crate::peripheral::DWT::set_sync_count_tap).
+    #[inline]
+    pub fn enable_sync_packets(&mut self, bit: bool) {
+        unsafe {
+            self.tcr.modify(|mut r| {
+                r.set_synena(bit);
+                r
+            });
+        }
+    }
+
+    /// Enables the stimulus ports selected by `mask` in Trace Enable Register `block`
+    ///
+    /// Bit `n` of `mask` enables port `32 * block + n`; the other ports keep their state. Writes to
+    /// disabled ports are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block` is not in the range `0..8`.
+    #[inline]
+    pub fn enable_ports(&mut self, block: usize, mask: u32) {
+        unsafe { self.ter[block].modify(|r| r | mask) }
+    }
+
+    /// Disables the stimulus ports selected by `mask` in Trace Enable Register `block`
+    ///
+    /// See [`ITM::enable_ports`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block` is not in the range `0..8`.
+    #[inline]
+    pub fn disable_ports(&mut self, block: usize, mask: u32) {
+        unsafe { self.ter[block].modify(|r| r & !mask) }
+    }
+
+    /// Selects the stimulus ports that unprivileged code can write to
+    ///
+    /// Access is controlled per group of 8 ports: bit `n` of `mask` allows unprivileged access to
+    /// ports `8 * n` to `8 * n + 7`; all the other ports can only be written by privileged code.
+    /// This overwrites the Trace Privilege Register. The bits for groups of ports that the ITM
+    /// doesn't implement are read-as-zero, write-ignored (e.g. only bits `0..4` on a Cortex-M3
+    /// or Cortex-M4, which have 32 ports).
+    #[inline]
+    pub fn set_unprivileged_ports(&mut self, mask: u32) {
+        // TPR has one "privileged access only" bit per group of 8 ports
+        unsafe { self.tpr.write(!mask) }
+    }
+
+    /// Configures the local timestamp generator
+    ///
+    /// Local timestamps are emitted after ITM and DWT packets and hold the number of timestamp
+    /// clock ticks elapsed since the previous local timestamp. The timestamp clock is either the
+    /// processor clock or the asynchronous TPIU clock (see [`TimestampClkSrc`] and
+    /// [`ITM::configure`]), divided by the prescaler selected by `options`. A host can thus only
+    /// convert timestamps into real time if it knows the frequency of that clock.
+    #[inline]
+    pub fn set_local_timestamps(&mut self, options: LocalTimestampOptions) {
+        unsafe {
+            self.tcr.modify(|mut r| {
+                set_local_timestamps(&mut r, options);
+                r
+            });
+        }
+    }
+
+    /// Configures how often global timestamps are generated
+    ///
+    /// Global timestamps are derived from a system-wide timestamp counter and allow correlating
+    /// the trace output of several trace sources.
+    #[inline]
+    pub fn set_global_timestamps(&mut self, options: GlobalTimestampOptions) {
+        unsafe {
+            self.tcr.modify(|mut r| {
+                set_global_timestamps(&mut r, options);
+                r
+            });
+        }
+    }
+}
+
+#[inline]
+fn set_local_timestamps(tcr: &mut Tcr, options: LocalTimestampOptions) {
+    tcr.set_tsena(options != LocalTimestampOptions::Disabled);
+    tcr.set_tsprescale(match options {
+        LocalTimestampOptions::Disabled | LocalTimestampOptions::Enabled => 0b00,
+        LocalTimestampOptions::EnabledDiv4 => 0b01,
+        LocalTimestampOptions::EnabledDiv16 => 0b10,
+        LocalTimestampOptions::EnabledDiv64 => 0b11,
+    });
+}
+
+#[inline]
+fn set_global_timestamps(tcr: &mut Tcr, options: GlobalTimestampOptions) {
+    tcr.set_gtsfreq(match options {
+        GlobalTimestampOptions::Disabled => 0b00,
+        GlobalTimestampOptions::Every128Cycles => 0b01,
+        GlobalTimestampOptions::Every8192Cycles => 0b10,
+        GlobalTimestampOptions::EveryPacket => 0b11,
+    });
 }
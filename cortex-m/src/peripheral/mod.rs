@@ -244,6 +244,16 @@ impl AC {
     pub const PTR: *const self::ac::RegisterBlock = 0xE000_EF90 as *const _;
 }
 
+#[cfg(cm7)]
+impl ops::Deref for AC {
+    type Target = self::ac::RegisterBlock;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+
 /// Cache and branch predictor maintenance operations
 #[allow(clippy::upper_case_acronyms)]
 pub struct CBP {
@@ -2,6 +2,8 @@
 
 use volatile_register::{RO, RW};
 
+use crate::peripheral::MPU;
+
 /// Register block for ARMv7-M
 #[cfg(not(armv8m))]
 #[repr(C)]
@@ -63,3 +65,162 @@ pub struct RegisterBlock {
     /// Memory Attribute Indirection register 0 and 1
     pub mair: [RW<u32>; 2],
 }
+
+/// Memory attributes of an ARMv7-M MPU region, as encoded in the upper half of RASR
+///
+/// The constructors encode the TEX, C, B and S bits for common memory types (see "Memory
+/// attributes" in the ARMv7-M Architecture Reference Manual, table B3-13). The resulting regions
+/// are not accessible until access permissions are set with [`RegionAttributes::access`].
+///
+/// The value returned by [`RegionAttributes::bits`] still has to be combined with the region
+/// SIZE and ENABLE fields before being written to RASR.
+#[cfg(not(armv8m))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegionAttributes {
+    bits: u32,
+}
+
+#[cfg(not(armv8m))]
+const RASR_XN: u32 = 1 << 28;
+#[cfg(not(armv8m))]
+const RASR_AP_SHIFT: u32 = 24;
+#[cfg(not(armv8m))]
+const RASR_AP_MASK: u32 = 0b111 << RASR_AP_SHIFT;
+#[cfg(not(armv8m))]
+const RASR_TEX_SHIFT: u32 = 19;
+#[cfg(not(armv8m))]
+const RASR_S: u32 = 1 << 18;
+#[cfg(not(armv8m))]
+const RASR_C: u32 = 1 << 17;
+#[cfg(not(armv8m))]
+const RASR_B: u32 = 1 << 16;
+#[cfg(not(armv8m))]
+const RASR_SRD_SHIFT: u32 = 8;
+#[cfg(not(armv8m))]
+const RASR_SRD_MASK: u32 = 0xff << RASR_SRD_SHIFT;
+
+#[cfg(not(armv8m))]
+impl RegionAttributes {
+    /// Normal memory, outer and inner write-back, write and read allocate
+    ///
+    /// This is the usual choice for RAM and Flash.
+    #[inline]
+    pub const fn normal_wb_wa() -> Self {
+        Self {
+            bits: (0b001 << RASR_TEX_SHIFT) | RASR_C | RASR_B,
+        }
+    }
+
+    /// Normal memory, outer and inner non-cacheable
+    ///
+    /// Useful for buffers shared with DMA masters on devices with a data cache.
+    #[inline]
+    pub const fn normal_non_cacheable() -> Self {
+        Self {
+            bits: 0b001 << RASR_TEX_SHIFT,
+        }
+    }
+
+    /// Shareable device memory
+    ///
+    /// This is the usual choice for memory-mapped peripherals.
+    #[inline]
+    pub const fn device_shared() -> Self {
+        Self {
+            bits: RASR_S | RASR_B,
+        }
+    }
+
+    /// Strongly-ordered memory
+    ///
+    /// Strongly-ordered memory is always shareable.
+    #[inline]
+    pub const fn strongly_ordered() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Marks the region as shareable
+    ///
+    /// This only has an effect on normal memory.
+    #[inline]
+    pub const fn shareable(self) -> Self {
+        Self {
+            bits: self.bits | RASR_S,
+        }
+    }
+
+    /// Sets the access permissions of the region
+    #[inline]
+    pub const fn access(self, access: AccessPermission) -> Self {
+        Self {
+            bits: (self.bits & !RASR_AP_MASK) | ((access as u32) << RASR_AP_SHIFT),
+        }
+    }
+
+    /// Forbids instruction fetches from the region
+    #[inline]
+    pub const fn execute_never(self) -> Self {
+        Self {
+            bits: self.bits | RASR_XN,
+        }
+    }
+
+    /// Disables the subregions whose bit is set in `srd`
+    ///
+    /// Each region of 256 bytes or more is split into 8 subregions of equal size; bit `n` of
+    /// `srd` disables subregion `n`.
+    #[inline]
+    pub const fn subregion_disable(self, srd: u8) -> Self {
+        Self {
+            bits: (self.bits & !RASR_SRD_MASK) | ((srd as u32) << RASR_SRD_SHIFT),
+        }
+    }
+
+    /// Returns the RASR bits encoding these attributes
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+}
+
+/// Access permissions of an ARMv7-M MPU region (RASR.AP)
+#[cfg(not(armv8m))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AccessPermission {
+    /// No access
+    NoAccess = 0b000,
+    /// Read/write in privileged mode, no access in unprivileged mode
+    PrivilegedReadWrite = 0b001,
+    /// Read/write in privileged mode, read-only in unprivileged mode
+    PrivilegedReadWriteUnprivilegedReadOnly = 0b010,
+    /// Read/write in both privileged and unprivileged mode
+    ReadWrite = 0b011,
+    /// Read-only in privileged mode, no access in unprivileged mode
+    PrivilegedReadOnly = 0b101,
+    /// Read-only in both privileged and unprivileged mode
+    ReadOnly = 0b110,
+}
+
+impl MPU {
+    /// Returns the number of regions implemented by the MPU (TYPE.DREGION)
+    ///
+    /// The regions are numbered `0..region_count()`; writes to RNR select a nonexistent region
+    /// otherwise. Returns `0` if the device doesn't implement an MPU.
+    #[inline]
+    pub fn region_count() -> u8 {
+        // NOTE(unsafe) atomic read with no side effects
+        let type_ = unsafe { (*Self::PTR)._type.read() };
+        (type_ >> 8) as u8
+    }
+
+    /// Returns `true` if the MPU has separate instruction and data regions (TYPE.SEPARATE)
+    ///
+    /// Otherwise the regions are unified, i.e. apply to both instruction and data accesses, which
+    /// is always the case on ARMv6-M, ARMv7-M and ARMv8-M.
+    #[inline]
+    pub fn has_separate_regions() -> bool {
+        // NOTE(unsafe) atomic read with no side effects
+        unsafe { (*Self::PTR)._type.read() & 1 != 0 }
+    }
+}
@@ -1,10 +1,14 @@
 //! Nested Vector Interrupt Controller
 
+use core::sync::atomic::{compiler_fence, Ordering};
+
 use volatile_register::RW;
 #[cfg(not(armv6m))]
 use volatile_register::{RO, WO};
 
 use crate::interrupt::InterruptNumber;
+#[cfg(not(any(armv6m, armv8m_base)))]
+use crate::peripheral::scb::PriorityGrouping;
 use crate::peripheral::NVIC;
 
 /// Register block
@@ -128,6 +132,27 @@ impl NVIC {
         (*Self::PTR).iser[usize::from(nr / 32)].write(1 << (nr % 32))
     }
 
+    /// Enables `interrupt`, checking in debug builds that its number is below `max`
+    ///
+    /// `max` is the number of external interrupts implemented by the device, which a HAL can
+    /// supply. This catches mis-sized interrupt enums during development; in release builds it is
+    /// the same as [`NVIC::unmask`].
+    ///
+    /// This function is `unsafe` because it can break mask-based critical sections
+    #[inline]
+    pub unsafe fn unmask_checked<I>(interrupt: I, max: u16)
+    where
+        I: InterruptNumber,
+    {
+        debug_assert!(
+            interrupt.number() < max,
+            "interrupt number {} is out of range (max {})",
+            interrupt.number(),
+            max
+        );
+        Self::unmask(interrupt)
+    }
+
     /// Returns the NVIC priority of `interrupt`
     ///
     /// *NOTE* NVIC encodes priority in the highest bits of a byte so values like `1` and `2` map
@@ -154,6 +179,30 @@ impl NVIC {
         }
     }
 
+    /// Returns `true` if interrupt `a` can preempt interrupt `b`
+    ///
+    /// Reads the priorities of both interrupts and compares their group (preemption) priorities,
+    /// as decoded by [`decode_priority`] for `grouping` and `prio_bits` (see that function). `a`
+    /// preempts `b` only if its group priority value is strictly lower; interrupts with the same
+    /// group priority never preempt each other, their subpriority only decides which one is
+    /// handled first when both are pending.
+    ///
+    /// `grouping` is usually the value returned by
+    /// [`SCB::get_priority_grouping`](crate::peripheral::SCB::get_priority_grouping).
+    ///
+    /// *NOTE* Not available on Armv6-M and Armv8-M Baseline, which don't support priority grouping.
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    #[inline]
+    pub fn would_preempt<I, J>(a: I, b: J, grouping: PriorityGrouping, prio_bits: u8) -> bool
+    where
+        I: InterruptNumber,
+        J: InterruptNumber,
+    {
+        let (a, _) = decode_priority(grouping, prio_bits, Self::get_priority(a));
+        let (b, _) = decode_priority(grouping, prio_bits, Self::get_priority(b));
+        a < b
+    }
+
     /// Is `interrupt` active or pre-empted and stacked
     #[cfg(not(armv6m))]
     #[inline]
@@ -194,6 +243,56 @@ impl NVIC {
         unsafe { ((*Self::PTR).ispr[usize::from(nr / 32)].read() & mask) == mask }
     }
 
+    /// Returns the active and pending state of `interrupt`
+    ///
+    /// This reads IABR and ISPR once each, and combines the two bits in a single value, instead of
+    /// calling [`NVIC::is_active`] and [`NVIC::is_pending`] separately.
+    #[cfg(not(armv6m))]
+    #[inline]
+    pub fn state<I>(interrupt: I) -> InterruptState
+    where
+        I: InterruptNumber,
+    {
+        let nr = interrupt.number();
+        let mask = 1 << (nr % 32);
+
+        // NOTE(unsafe) atomic reads with no side effects
+        let (active, pending) = unsafe {
+            let nvic = &*Self::PTR;
+            (
+                nvic.iabr[usize::from(nr / 32)].read() & mask == mask,
+                nvic.ispr[usize::from(nr / 32)].read() & mask == mask,
+            )
+        };
+
+        match (active, pending) {
+            (false, false) => InterruptState::Inactive,
+            (false, true) => InterruptState::Pending,
+            (true, false) => InterruptState::Active,
+            (true, true) => InterruptState::ActivePending,
+        }
+    }
+
+    /// Returns an iterator over the numbers of all pending interrupts, in ascending order
+    ///
+    /// The pending state is read one ISPR word (32 interrupts) at a time as the iterator
+    /// advances, so interrupts that become pending or are cleared during the iteration may or
+    /// may not be reported.
+    #[inline]
+    pub fn pending_iter() -> InterruptIter {
+        // NOTE(unsafe) atomic reads with no side effects
+        InterruptIter::new(unsafe { &(*Self::PTR).ispr })
+    }
+
+    /// Returns an iterator over the numbers of all enabled interrupts, in ascending order
+    ///
+    /// See [`NVIC::pending_iter`] for how concurrent changes are handled.
+    #[inline]
+    pub fn enabled_iter() -> InterruptIter {
+        // NOTE(unsafe) atomic reads with no side effects
+        InterruptIter::new(unsafe { &(*Self::PTR).iser })
+    }
+
     /// Forces `interrupt` into pending state
     #[inline]
     pub fn pend<I>(interrupt: I)
@@ -240,6 +339,90 @@ impl NVIC {
         }
     }
 
+    /// Copies the priority of every interrupt into `buf`, indexed by interrupt number
+    ///
+    /// Together with [`NVIC::restore_priorities`] this allows, for example, a bootloader to hand
+    /// over a clean interrupt configuration and to reinstate its own configuration afterwards.
+    #[inline]
+    pub fn save_priorities(&self, buf: &mut [u8; NUM_PRIORITIES]) {
+        #[cfg(not(armv6m))]
+        for (prio, ipr) in buf.iter_mut().zip(self.ipr.iter()) {
+            *prio = ipr.read();
+        }
+
+        #[cfg(armv6m)]
+        for (prios, ipr) in buf.chunks_mut(4).zip(self.ipr.iter()) {
+            prios.copy_from_slice(&ipr.read().to_le_bytes());
+        }
+    }
+
+    /// Sets the priority of every interrupt from `buf`, indexed by interrupt number
+    ///
+    /// # Unsafety
+    ///
+    /// Changing priority levels can break priority-based critical sections (see
+    /// [`register::basepri`](crate::register::basepri)) and compromise memory safety.
+    #[inline]
+    pub unsafe fn restore_priorities(&mut self, buf: &[u8; NUM_PRIORITIES]) {
+        #[cfg(not(armv6m))]
+        for (prio, ipr) in buf.iter().zip(self.ipr.iter()) {
+            ipr.write(*prio);
+        }
+
+        #[cfg(armv6m)]
+        for (prios, ipr) in buf.chunks(4).zip(self.ipr.iter()) {
+            ipr.write(u32::from_le_bytes([prios[0], prios[1], prios[2], prios[3]]));
+        }
+    }
+
+    /// Copies the enable state of every interrupt into `buf`, one bit per interrupt, in the
+    /// same layout as the ISER registers
+    #[inline]
+    pub fn save_enabled(&self, buf: &mut [u32; 16]) {
+        for (word, iser) in buf.iter_mut().zip(self.iser[..NVIC_WORDS].iter()) {
+            *word = iser.read();
+        }
+    }
+
+    /// Enables exactly the interrupts whose bit is set in `buf` and disables all the others
+    ///
+    /// `buf` has the layout produced by [`NVIC::save_enabled`].
+    ///
+    /// # Unsafety
+    ///
+    /// This can unmask interrupts, which can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn restore_enabled(&mut self, buf: &[u32; 16]) {
+        for ((word, icer), iser) in buf
+            .iter()
+            .zip(self.icer.iter())
+            .zip(self.iser.iter())
+            .take(NVIC_WORDS)
+        {
+            icer.write(!*word);
+            iser.write(*word);
+        }
+    }
+
+    /// Disables every interrupt and clears every pending interrupt
+    ///
+    /// Writes all ones to every ICER and ICPR register, then issues the barriers required for the
+    /// interrupts to be disabled before the next instruction, e.g. before a bootloader jumps to
+    /// the application. Interrupt priorities are not reset; use [`NVIC::restore_priorities`] for
+    /// that.
+    #[inline]
+    pub fn reset_all(&mut self) {
+        for (icer, icpr) in self.icer.iter().zip(self.icpr.iter()).take(NVIC_WORDS) {
+            // NOTE(unsafe) atomic stateless writes; ICER and ICPR don't store any state
+            unsafe {
+                icer.write(0xffff_ffff);
+                icpr.write(0xffff_ffff);
+            }
+        }
+
+        crate::asm::barrier_after_config_write();
+    }
+
     /// Clears `interrupt`'s pending state
     #[inline]
     pub fn unpend<I>(interrupt: I)
@@ -252,6 +435,115 @@ impl NVIC {
         unsafe { (*Self::PTR).icpr[usize::from(nr / 32)].write(1 << (nr % 32)) }
     }
 
+    /// Forces `interrupt` into pending state, then enables it
+    ///
+    /// The interrupt is pended *before* it is enabled, so a pending state left over from before
+    /// this call is merged with the new one and the handler runs once, instead of running for the
+    /// stale request as soon as the interrupt is enabled and then again for the new one.
+    ///
+    /// This function is `unsafe` because it can break mask-based critical sections
+    #[inline]
+    pub unsafe fn pend_and_unmask<I>(interrupt: I)
+    where
+        I: InterruptNumber,
+    {
+        Self::pend(interrupt);
+        Self::unmask(interrupt);
+    }
+
+    /// Disables `interrupt`, then clears its pending state
+    ///
+    /// The interrupt is disabled *before* its pending state is cleared, so it can't become
+    /// pending and fire in between: once this function returns the interrupt is neither enabled
+    /// nor pending.
+    #[inline]
+    pub fn mask_and_unpend<I>(interrupt: I)
+    where
+        I: InterruptNumber,
+    {
+        Self::mask(interrupt);
+        Self::unpend(interrupt);
+    }
+
+    /// Selects the security state that handles `interrupt`
+    ///
+    /// Writes the interrupt's bit in the Interrupt Target Non-secure registers (ITNS). Secure
+    /// firmware uses this to hand interrupts over to the Non-secure world.
+    ///
+    /// *NOTE* ITNS is only accessible from Secure state. From Non-secure state it reads as zero
+    /// and writes are ignored.
+    #[cfg(armv8m)]
+    #[inline]
+    pub fn set_target_state<I>(&mut self, interrupt: I, state: TargetState)
+    where
+        I: InterruptNumber,
+    {
+        let nr = interrupt.number();
+        let mask = 1 << (nr % 32);
+
+        unsafe {
+            self.itns[usize::from(nr / 32)].modify(|value| match state {
+                TargetState::Secure => value & !mask,
+                TargetState::NonSecure => value | mask,
+            })
+        }
+    }
+
+    /// Returns the security state that handles `interrupt`
+    ///
+    /// See [`NVIC::set_target_state`]. From Non-secure state this always returns
+    /// [`TargetState::Secure`].
+    #[cfg(armv8m)]
+    #[inline]
+    pub fn target_state<I>(interrupt: I) -> TargetState
+    where
+        I: InterruptNumber,
+    {
+        let nr = interrupt.number();
+        let mask = 1 << (nr % 32);
+
+        // NOTE(unsafe) atomic read with no side effects
+        if unsafe { (*Self::PTR).itns[usize::from(nr / 32)].read() } & mask == mask {
+            TargetState::NonSecure
+        } else {
+            TargetState::Secure
+        }
+    }
+
+    /// Execute closure `f` with `interrupt` disabled
+    ///
+    /// Disables `interrupt`, runs `f` and then re-enables `interrupt`, but only if it was enabled
+    /// before. Every other interrupt stays enabled. This is useful on ARMv6-M, which has no
+    /// BASEPRI, to protect data shared with a single interrupt handler (see
+    /// [`interrupt::free_below`](crate::interrupt::free_below) for the BASEPRI-based alternative).
+    ///
+    /// *NOTE* Disabling an interrupt doesn't clear its pending state. If `interrupt` is triggered
+    /// while `f` runs (or was already pending), its handler runs as soon as it is re-enabled.
+    #[inline]
+    pub fn free_interrupt<I, F, R>(interrupt: I, f: F) -> R
+    where
+        I: InterruptNumber,
+        F: FnOnce() -> R,
+    {
+        let enabled = Self::is_enabled(interrupt);
+        Self::mask(interrupt);
+
+        // Ensure the interrupt is disabled before `f` accesses the shared data.
+        crate::asm::barrier_after_config_write();
+
+        let r = f();
+
+        // Ensure no preceeding memory accesses are reordered to after the interrupt is enabled.
+        compiler_fence(Ordering::SeqCst);
+
+        if enabled {
+            // NOTE(unsafe) the interrupt was enabled before, so this restores the previous state
+            unsafe { Self::unmask(interrupt) };
+        }
+
+        r
+    }
+
     #[cfg(armv6m)]
     #[inline]
     fn ipr_index<I>(interrupt: I) -> usize
@@ -270,3 +562,256 @@ impl NVIC {
         (usize::from(interrupt.number()) % 4) * 8
     }
 }
+
+/// Number of interrupt priorities handled by [`NVIC::save_priorities`] and
+/// [`NVIC::restore_priorities`]
+///
+/// This is the maximum number of interrupts supported by the architecture: 496 on ARMv7-M and
+/// ARMv8-M, 32 on ARMv6-M.
+#[cfg(not(armv6m))]
+pub const NUM_PRIORITIES: usize = 496;
+/// Number of interrupt priorities handled by [`NVIC::save_priorities`] and
+/// [`NVIC::restore_priorities`]
+///
+/// This is the maximum number of interrupts supported by the architecture: 496 on ARMv7-M and
+/// ARMv8-M, 32 on ARMv6-M.
+#[cfg(armv6m)]
+pub const NUM_PRIORITIES: usize = 32;
+
+/// Priorities of the first `N` interrupts, indexed by interrupt number
+///
+/// This allows expressing the priority configuration of all interrupts declaratively, e.g. as a
+/// `const`, and applying it in one pass with [`PriorityTable::apply`]. `N` must not exceed
+/// [`NUM_PRIORITIES`]; this is checked at compile time.
+///
+/// *NOTE*: Hardware priority does not exactly match logical priority levels. See
+/// [`NVIC.get_priority`](struct.NVIC.html#method.get_priority) for more details.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PriorityTable<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> PriorityTable<N> {
+    const FITS: () = assert!(
+        N <= NUM_PRIORITIES,
+        "PriorityTable has more entries than NUM_PRIORITIES"
+    );
+
+    /// Creates a table from the priorities of interrupts `0..N`
+    #[inline]
+    pub const fn new(priorities: [u8; N]) -> Self {
+        let () = Self::FITS;
+
+        PriorityTable(priorities)
+    }
+
+    /// Returns the priority of `interrupt` in the table
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `interrupt` is not less than `N`.
+    #[inline]
+    pub fn get<I>(&self, interrupt: I) -> u8
+    where
+        I: InterruptNumber,
+    {
+        self.0[usize::from(interrupt.number())]
+    }
+
+    /// Sets the priority of `interrupt` in the table
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `interrupt` is not less than `N`.
+    #[inline]
+    pub fn set<I>(&mut self, interrupt: I, prio: u8)
+    where
+        I: InterruptNumber,
+    {
+        self.0[usize::from(interrupt.number())] = prio;
+    }
+
+    /// Writes all the priorities in the table to the NVIC
+    ///
+    /// Four priorities are written with each 32-bit store, so this is much faster than calling
+    /// [`NVIC::set_priority`] for each interrupt. If `N` is not a multiple of 4, the priorities of
+    /// the interrupts sharing the last IPR word with the table, but not in it, are preserved.
+    ///
+    /// # Unsafety
+    ///
+    /// Changing priority levels can break priority-based critical sections (see
+    /// [`register::basepri`](crate::register::basepri)) and compromise memory safety.
+    #[inline]
+    pub unsafe fn apply(&self, nvic: &mut NVIC) {
+        let () = Self::FITS;
+
+        for (i, prios) in self.0.chunks(4).enumerate() {
+            #[cfg(not(armv6m))]
+            {
+                if let [a, b, c, d] = *prios {
+                    // IPR registers are word accessible; write 4 priorities at once
+                    let ipr = core::ptr::addr_of!(nvic.ipr[4 * i]) as *mut u32;
+                    core::ptr::write_volatile(ipr, u32::from_le_bytes([a, b, c, d]));
+                } else {
+                    for (j, prio) in prios.iter().enumerate() {
+                        nvic.ipr[4 * i + j].write(*prio);
+                    }
+                }
+            }
+
+            #[cfg(armv6m)]
+            {
+                if let [a, b, c, d] = *prios {
+                    nvic.ipr[i].write(u32::from_le_bytes([a, b, c, d]));
+                } else {
+                    nvic.ipr[i].modify(|value| {
+                        let mut bytes = value.to_le_bytes();
+                        bytes[..prios.len()].copy_from_slice(prios);
+                        u32::from_le_bytes(bytes)
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Active and pending state of an interrupt, as returned by [`NVIC::state`]
+#[cfg(not(armv6m))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InterruptState {
+    /// Neither active nor pending
+    Inactive,
+    /// Waiting to be serviced
+    Pending,
+    /// Being serviced, or preempted by a higher priority exception and stacked
+    Active,
+    /// Active, and pending again
+    ///
+    /// The interrupt was re-triggered (or pended by software) while its handler was running. The
+    /// handler will run again once the current invocation returns, unless the pending bit is
+    /// cleared first.
+    ActivePending,
+}
+
+#[cfg(not(armv6m))]
+impl InterruptState {
+    /// Is the interrupt active, i.e. `Active` or `ActivePending`?
+    #[inline]
+    pub fn is_active(self) -> bool {
+        matches!(self, InterruptState::Active | InterruptState::ActivePending)
+    }
+
+    /// Is the interrupt pending, i.e. `Pending` or `ActivePending`?
+    #[inline]
+    pub fn is_pending(self) -> bool {
+        matches!(
+            self,
+            InterruptState::Pending | InterruptState::ActivePending
+        )
+    }
+}
+
+/// Security state that handles an interrupt, see [`NVIC::set_target_state`]
+#[cfg(armv8m)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TargetState {
+    /// The interrupt targets Secure state (reset value)
+    Secure,
+    /// The interrupt targets Non-secure state
+    NonSecure,
+}
+
+/// Number of implemented words in each of the NVIC bit arrays (ISER, ICER, ISPR, ...)
+#[cfg(armv6m)]
+const NVIC_WORDS: usize = 1;
+#[cfg(not(armv6m))]
+const NVIC_WORDS: usize = 16;
+
+/// Iterator over the interrupt numbers whose bit is set in one of the NVIC bit arrays
+///
+/// Returned by [`NVIC::pending_iter`] and [`NVIC::enabled_iter`].
+pub struct InterruptIter {
+    words: &'static [RW<u32>],
+    index: usize,
+    current: u32,
+}
+
+impl InterruptIter {
+    #[inline]
+    fn new(words: &'static [RW<u32>; 16]) -> Self {
+        InterruptIter {
+            words: &words[..NVIC_WORDS],
+            index: 0,
+            current: 0,
+        }
+    }
+}
+
+impl Iterator for InterruptIter {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        while self.current == 0 {
+            self.current = self.words.get(self.index)?.read();
+            self.index += 1;
+        }
+
+        let bit = self.current.trailing_zeros();
+        // clear the lowest set bit
+        self.current &= self.current - 1;
+
+        Some(((self.index - 1) * 32) as u16 + bit as u16)
+    }
+}
+
+/// Encodes a group (preemption) priority and a subpriority into a hardware priority value
+///
+/// `prio_bits` is the number of priority bits implemented by the device (usually provided by the
+/// device crate as `NVIC_PRIO_BITS`). The fields are laid out according to `grouping`; if the
+/// device implements fewer than 8 priority bits the subpriority field loses its least significant
+/// bits first.
+///
+/// `preempt` and `sub` are saturated to the largest value that fits their field.
+///
+/// The result can be passed to [`NVIC::set_priority`] or
+/// [`SCB::set_priority`](crate::peripheral::SCB::set_priority).
+///
+/// *NOTE* Not available on Armv6-M and Armv8-M Baseline, which don't support priority grouping.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[inline]
+pub fn encode_priority(grouping: PriorityGrouping, prio_bits: u8, preempt: u8, sub: u8) -> u8 {
+    let (group_bits, sub_bits, prio_bits) = priority_fields(grouping, prio_bits);
+
+    let preempt = u32::from(preempt).min((1 << group_bits) - 1);
+    let sub = u32::from(sub).min((1 << sub_bits) - 1);
+
+    ((preempt << (8 - group_bits)) | (sub << (8 - prio_bits))) as u8
+}
+
+/// Decodes a hardware priority value into its group (preemption) priority and subpriority
+///
+/// This is the inverse of [`encode_priority`]; see that function for the meaning of the
+/// arguments. Unimplemented priority bits are ignored.
+///
+/// *NOTE* Not available on Armv6-M and Armv8-M Baseline, which don't support priority grouping.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[inline]
+pub fn decode_priority(grouping: PriorityGrouping, prio_bits: u8, raw: u8) -> (u8, u8) {
+    let (group_bits, sub_bits, prio_bits) = priority_fields(grouping, prio_bits);
+
+    let raw = u32::from(raw);
+    let preempt = (raw >> (8 - group_bits)) & ((1 << group_bits) - 1);
+    let sub = (raw >> (8 - prio_bits)) & ((1 << sub_bits) - 1);
+
+    (preempt as u8, sub as u8)
+}
+
+/// Returns the number of implemented group priority bits, implemented subpriority bits and
+/// implemented priority bits (clamped to 8)
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[inline]
+fn priority_fields(grouping: PriorityGrouping, prio_bits: u8) -> (u32, u32, u32) {
+    let prio_bits = u32::from(prio_bits.min(8));
+    let group_bits = u32::from(grouping.group_bits()).min(prio_bits);
+
+    (group_bits, prio_bits - group_bits, prio_bits)
+}
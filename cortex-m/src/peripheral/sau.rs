@@ -102,7 +102,7 @@ bitfield! {
 }
 
 /// Possible attribute of a SAU region.
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum SauRegionAttribute {
     /// SAU region is Secure
     Secure,
@@ -124,7 +124,7 @@ pub struct SauRegion {
 }
 
 /// Possible error values returned by the SAU methods.
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum SauError {
     /// The region number parameter to set or get a region must be between 0 and
     /// region_numbers() - 1.
@@ -1,5 +1,7 @@
 //! System Control Block
 
+#[cfg(not(any(armv6m, armv8m_base)))]
+use core::fmt;
 use core::ptr;
 
 use volatile_register::RW;
@@ -195,6 +197,74 @@ impl SCB {
     }
 }
 
+impl SCB {
+    /// Returns the vector table offset, i.e. the address of the active vector table
+    ///
+    /// *NOTE* VTOR is optional on ARMv6-M. On devices that don't implement it the register reads
+    /// as zero, and the vector table is always located at address `0x0000_0000`.
+    #[inline]
+    pub fn vector_table_offset() -> u32 {
+        // NOTE(unsafe) atomic read with no side effects
+        unsafe { (*Self::PTR).vtor.read() }
+    }
+
+    /// Copies `count` vectors from `rom_table` to `ram_table` and makes `ram_table` the active
+    /// vector table
+    ///
+    /// Afterwards, individual handlers can be replaced at runtime with [`SCB::set_handler`].
+    ///
+    /// VTOR ignores the low bits of the address written to it, so the vector table must be
+    /// aligned to the table size rounded up to the next power of two, and to at least 128 bytes:
+    /// e.g. a table of 16 system exceptions and 100 interrupts (116 vectors, 464 bytes) must be
+    /// 512-byte aligned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ram_table` holds fewer than `count` vectors or is not sufficiently aligned.
+    ///
+    /// # Safety
+    ///
+    /// - `rom_table` must be valid for reads of `count` words, and should be the currently active
+    ///   vector table (or an equivalent one).
+    /// - `count` must cover every exception and interrupt that can occur.
+    /// - VTOR must be implemented (it is optional on ARMv6-M).
+    #[inline]
+    pub unsafe fn relocate_vector_table(
+        &mut self,
+        ram_table: &'static mut [u32],
+        rom_table: *const u32,
+        count: usize,
+    ) {
+        assert!(ram_table.len() >= count);
+        let align = (count * 4).next_power_of_two().max(128);
+        assert!(ram_table.as_ptr() as usize % align == 0);
+
+        ram_table[..count].copy_from_slice(core::slice::from_raw_parts(rom_table, count));
+
+        crate::asm::dsb();
+        self.vtor.write(ram_table.as_ptr() as u32);
+        crate::asm::barrier_after_config_write();
+    }
+
+    /// Replaces vector `index` of the active vector table with `handler`
+    ///
+    /// `index` is the exception number, i.e. `16 + n` for interrupt `n`. `handler` is the address
+    /// of the new handler function, with the Thumb bit set.
+    ///
+    /// # Safety
+    ///
+    /// - The active vector table must be in RAM, e.g. after [`SCB::relocate_vector_table`], and
+    ///   `index` must be within it.
+    /// - `handler` must be a valid exception handler. If the exception can be taken while it's
+    ///   being replaced, both the old and the new handler must be prepared for it.
+    #[inline]
+    pub unsafe fn set_handler(&mut self, index: usize, handler: u32) {
+        let table = self.vtor.read() as *mut u32;
+        ptr::write_volatile(table.add(index), handler);
+        crate::asm::dsb();
+    }
+}
+
 /// Processor core exceptions (internal interrupts)
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -368,8 +438,7 @@ impl SCB {
         // Invalidate I-cache
         cbp.iciallu();
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Returns whether the I-cache is currently enabled.
@@ -391,8 +460,7 @@ impl SCB {
         // Invalidate I-cache
         cbp.iciallu();
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Enables D-cache if currently disabled.
@@ -473,8 +541,7 @@ impl SCB {
             }
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Cleans the entire D-cache.
@@ -495,8 +562,7 @@ impl SCB {
             }
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Cleans and invalidates the entire D-cache.
@@ -518,8 +584,7 @@ impl SCB {
             }
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Invalidates D-cache by address.
@@ -587,8 +652,7 @@ impl SCB {
             addr += line_size;
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Invalidates an object from the D-cache.
@@ -712,8 +776,7 @@ impl SCB {
             addr += line_size;
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 
     /// Cleans an object from the D-cache.
@@ -787,8 +850,7 @@ impl SCB {
             addr += LINESIZE;
         }
 
-        crate::asm::dsb();
-        crate::asm::isb();
+        crate::asm::barrier_after_config_write();
     }
 }
 
@@ -832,12 +894,45 @@ impl SCB {
     }
 }
 
+const SCB_SCR_SEVONPEND: u32 = 0x1 << 4;
+
+impl SCB {
+    /// Set the SEVONPEND bit in the SCR register
+    ///
+    /// While set, an interrupt becoming pending generates a wakeup event, even if it is disabled
+    /// in the NVIC or masked by PRIMASK or BASEPRI. This lets an idle loop based on
+    /// [`asm::wfe`](crate::asm::wfe) wake up on interrupts it has masked, just like
+    /// [`asm::wfi`](crate::asm::wfi) does. Events from [`asm::sev`](crate::asm::sev) wake up `wfe`
+    /// regardless of this bit.
+    #[inline]
+    pub fn set_sevonpend(&mut self) {
+        unsafe {
+            self.scr.modify(|scr| scr | SCB_SCR_SEVONPEND);
+        }
+    }
+
+    /// Clear the SEVONPEND bit in the SCR register
+    ///
+    /// Only interrupts that are enabled and not masked generate a wakeup event.
+    #[inline]
+    pub fn clear_sevonpend(&mut self) {
+        unsafe {
+            self.scr.modify(|scr| scr & !SCB_SCR_SEVONPEND);
+        }
+    }
+}
+
 const SCB_AIRCR_VECTKEY: u32 = 0x05FA << 16;
 const SCB_AIRCR_PRIGROUP_MASK: u32 = 0x7 << 8;
 const SCB_AIRCR_SYSRESETREQ: u32 = 1 << 2;
+#[cfg(armv7m)]
+const SCB_AIRCR_VECTRESET: u32 = 1 << 0;
 
 impl SCB {
     /// Initiate a system reset request to reset the MCU
+    ///
+    /// This requests a reset of the whole system: the processor and, depending on the device,
+    /// its peripherals. See [`SCB::vect_reset`] for a reset of the processor core only.
     #[inline]
     pub fn sys_reset() -> ! {
         crate::asm::dsb();
@@ -858,6 +953,184 @@ impl SCB {
     }
 }
 
+#[cfg(armv7m)]
+impl SCB {
+    /// Resets the processor core only, leaving the rest of the system untouched
+    ///
+    /// Sets AIRCR.VECTRESET, which resets the core (except the debug logic) without resetting
+    /// the peripherals, unlike [`SCB::sys_reset`]. This is meant for debug scenarios where the
+    /// state of the peripherals must be kept.
+    ///
+    /// *NOTE* VECTRESET only exists on ARMv7-M (it was removed in ARMv8-M, and ARMv6-M never had
+    /// it). ARM recommends using it only while the core is halted by a debugger; the effect of
+    /// using it otherwise, or at the same time as SYSRESETREQ, is UNPREDICTABLE. Some vendors
+    /// don't support it at all.
+    #[inline]
+    pub fn vect_reset() -> ! {
+        crate::asm::dsb();
+        unsafe {
+            (*Self::PTR).aircr.modify(|r| {
+                SCB_AIRCR_VECTKEY // otherwise the write is ignored
+                    | r & SCB_AIRCR_PRIGROUP_MASK // keep priority group unchanged
+                    | SCB_AIRCR_VECTRESET
+            })
+        };
+        crate::asm::dsb();
+        loop {
+            // wait for the reset
+            crate::asm::nop(); // avoid rust-lang/rust#28728
+        }
+    }
+}
+
+/// Priority grouping, the split of exception priority values into a group (preemption) priority
+/// field and a subpriority field, as configured in AIRCR.PRIGROUP
+///
+/// Only the group priority determines whether an exception preempts the one that is currently
+/// active; the subpriority is only used to order pending exceptions with equal group priority.
+///
+/// Each variant is named after the number of bits of the 8-bit priority value used for the
+/// group priority and for the subpriority. If the device implements fewer than 8 priority bits,
+/// the unimplemented (least significant) bits are taken from the subpriority field first.
+///
+/// *NOTE* Not available on Armv6-M and Armv8-M Baseline, which don't support priority grouping.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum PriorityGrouping {
+    /// 7 bits of group priority, 1 bit of subpriority (reset value)
+    Group7Sub1 = 0,
+    /// 6 bits of group priority, 2 bits of subpriority
+    Group6Sub2 = 1,
+    /// 5 bits of group priority, 3 bits of subpriority
+    Group5Sub3 = 2,
+    /// 4 bits of group priority, 4 bits of subpriority
+    Group4Sub4 = 3,
+    /// 3 bits of group priority, 5 bits of subpriority
+    Group3Sub5 = 4,
+    /// 2 bits of group priority, 6 bits of subpriority
+    Group2Sub6 = 5,
+    /// 1 bit of group priority, 7 bits of subpriority
+    Group1Sub7 = 6,
+    /// No group priority, 8 bits of subpriority
+    Group0Sub8 = 7,
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl PriorityGrouping {
+    /// Converts a PRIGROUP field value into a `PriorityGrouping`
+    ///
+    /// Only the 3 least significant bits of `prigroup` are used.
+    #[inline]
+    pub fn from_prigroup(prigroup: u8) -> Self {
+        match prigroup & 0b111 {
+            0 => PriorityGrouping::Group7Sub1,
+            1 => PriorityGrouping::Group6Sub2,
+            2 => PriorityGrouping::Group5Sub3,
+            3 => PriorityGrouping::Group4Sub4,
+            4 => PriorityGrouping::Group3Sub5,
+            5 => PriorityGrouping::Group2Sub6,
+            6 => PriorityGrouping::Group1Sub7,
+            _ => PriorityGrouping::Group0Sub8,
+        }
+    }
+
+    /// Returns the value of the PRIGROUP field for this grouping
+    #[inline]
+    pub fn prigroup(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the number of bits of the priority value used for the subpriority
+    #[inline]
+    pub fn sub_bits(self) -> u8 {
+        self.prigroup() + 1
+    }
+
+    /// Returns the number of bits of the priority value used for the group priority
+    #[inline]
+    pub fn group_bits(self) -> u8 {
+        8 - self.sub_bits()
+    }
+
+    /// Returns `true` if all the group priority bits of this grouping are implemented by a
+    /// device with `prio_bits` priority bits (see [`SCB::priority_bits`])
+    ///
+    /// A grouping with more group priority bits than that behaves like one that uses all the
+    /// implemented bits for the group priority, which is most likely a configuration mistake.
+    /// `Group7Sub1` is always accepted, since it's the reset value.
+    #[inline]
+    pub fn fits_priority_bits(self, prio_bits: u8) -> bool {
+        self == PriorityGrouping::Group7Sub1 || self.sub_bits() >= 8 - prio_bits.min(8)
+    }
+}
+
+const SCB_AIRCR_VECTKEYSTAT: u32 = 0xFA05 << 16;
+const SCB_AIRCR_VECTKEYSTAT_MASK: u32 = 0xFFFF << 16;
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const SCB_AIRCR_PRIGROUP_SHIFT: u32 = 8;
+
+impl SCB {
+    /// Checks that AIRCR reads back the VECTKEYSTAT value (`0xFA05`) mandated by the architecture
+    ///
+    /// This can be used as a sanity check that the System Control Block is accessible.
+    #[inline]
+    pub fn is_aircr_vectkeystat_valid() -> bool {
+        // NOTE(unsafe) atomic read with no side effects
+        unsafe { (*Self::PTR).aircr.read() & SCB_AIRCR_VECTKEYSTAT_MASK == SCB_AIRCR_VECTKEYSTAT }
+    }
+
+    /// Returns the priority grouping configured in AIRCR.PRIGROUP
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    #[inline]
+    pub fn get_priority_grouping() -> PriorityGrouping {
+        // NOTE(unsafe) atomic read with no side effects
+        let aircr = unsafe { (*Self::PTR).aircr.read() };
+        let prigroup = (aircr & SCB_AIRCR_PRIGROUP_MASK) >> SCB_AIRCR_PRIGROUP_SHIFT;
+        PriorityGrouping::from_prigroup(prigroup as u8)
+    }
+
+    /// Sets the priority grouping in AIRCR.PRIGROUP
+    ///
+    /// The write always includes the VECTKEY required by AIRCR (without it the write would be
+    /// silently ignored) and preserves the other AIRCR fields. See [`PriorityGrouping`] for the
+    /// encoding.
+    ///
+    /// In debug builds this asserts that `grouping` doesn't have more group priority bits than
+    /// the device implements (see [`SCB::priority_bits`] and
+    /// [`PriorityGrouping::fits_priority_bits`]), e.g. `Group5Sub3` on a device with 4 priority
+    /// bits. `Group7Sub1`, the reset value, is always accepted.
+    ///
+    /// # Unsafety
+    ///
+    /// Changing the priority grouping changes which exceptions can preempt each other, which can
+    /// break priority-based critical sections (see [`register::basepri`](crate::register::basepri))
+    /// and compromise memory safety.
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    #[inline]
+    pub unsafe fn set_priority_grouping(&mut self, grouping: PriorityGrouping) {
+        #[cfg(debug_assertions)]
+        {
+            let prio_bits = self.priority_bits();
+            debug_assert!(
+                grouping.fits_priority_bits(prio_bits),
+                "{:?} has more group priority bits than implemented ({} priority bits)",
+                grouping,
+                prio_bits
+            );
+        }
+
+        self.aircr.modify(|r| {
+            SCB_AIRCR_VECTKEY | // otherwise the write is ignored
+            (r & !(SCB_AIRCR_VECTKEYSTAT_MASK | SCB_AIRCR_PRIGROUP_MASK)) |
+            (u32::from(grouping.prigroup()) << SCB_AIRCR_PRIGROUP_SHIFT)
+        });
+    }
+}
+
+const SCB_ICSR_NMIPENDSET: u32 = 1 << 31;
+
 const SCB_ICSR_PENDSVSET: u32 = 1 << 28;
 const SCB_ICSR_PENDSVCLR: u32 = 1 << 27;
 
@@ -908,6 +1181,24 @@ impl SCB {
             (*Self::PTR).icsr.write(SCB_ICSR_PENDSTCLR);
         }
     }
+
+    /// Set the NMIPENDSET bit in the ICSR register which will pend the NonMaskableInt exception
+    ///
+    /// This is the only way to raise NMI from software. NMI can't be masked, so the handler runs
+    /// right away regardless of PRIMASK, FAULTMASK and BASEPRI, preempting any other exception
+    /// handler (except a running NMI handler).
+    #[inline]
+    pub fn set_pendnmi() {
+        unsafe {
+            (*Self::PTR).icsr.write(SCB_ICSR_NMIPENDSET);
+        }
+    }
+
+    /// Check if NMIPENDSET bit in the ICSR register is set meaning NonMaskableInt is pending
+    #[inline]
+    pub fn is_pendnmi_pending() -> bool {
+        unsafe { (*Self::PTR).icsr.read() & SCB_ICSR_NMIPENDSET == SCB_ICSR_NMIPENDSET }
+    }
 }
 
 /// System handlers, exceptions with configurable priority
@@ -1027,6 +1318,24 @@ impl SCB {
         }
     }
 
+    /// Returns the number of priority bits implemented by the device
+    ///
+    /// This is probed by writing `0xFF` to the priority of the PendSV exception and counting
+    /// the bits that read back as set; the original priority is restored afterwards. Interrupts
+    /// are disabled during the probe, so the temporary priority is never observed.
+    #[inline]
+    pub fn priority_bits(&mut self) -> u8 {
+        crate::interrupt::free(|| {
+            let prio = Self::get_priority(SystemHandler::PendSV);
+            unsafe {
+                self.set_priority(SystemHandler::PendSV, 0xff);
+                let bits = Self::get_priority(SystemHandler::PendSV).leading_ones() as u8;
+                self.set_priority(SystemHandler::PendSV, prio);
+                bits
+            }
+        })
+    }
+
     /// Return the bit position of the exception enable bit in the SHCSR register
     #[inline]
     #[cfg(not(any(armv6m, armv8m_base)))]
@@ -1083,6 +1392,31 @@ impl SCB {
         }
     }
 
+    /// Enables or disables the MemManage, BusFault and UsageFault handlers at once
+    ///
+    /// The handlers selected in `handlers` are enabled, the others are disabled, in a single
+    /// SHCSR write. A fault whose handler is disabled escalates to HardFault. See
+    /// [`SCB::enable`] and [`SCB::disable`] to change a single handler.
+    #[inline]
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    pub fn set_fault_handlers_enabled(&mut self, handlers: FaultHandlers) {
+        let mask = 0b111 << 16;
+        let bits = u32::from(handlers.mem_manage) << 16
+            | u32::from(handlers.bus_fault) << 17
+            | u32::from(handlers.usage_fault) << 18;
+
+        unsafe { self.shcsr.modify(|value| (value & !mask) | bits) }
+    }
+
+    /// Disables the MemManage, BusFault and UsageFault handlers
+    ///
+    /// This restores the reset behaviour, where all these faults escalate to HardFault.
+    #[inline]
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    pub fn reset_fault_handlers(&mut self) {
+        self.set_fault_handlers_enabled(FaultHandlers::default())
+    }
+
     /// Check if an exception is enabled
     ///
     /// This function is only allowed on the following exception:
@@ -1102,3 +1436,420 @@ impl SCB {
         }
     }
 }
+
+/// Contents of the System Handler Control and State Register (SHCSR)
+///
+/// Tells which system handlers are active (running or preempted) and which faults are pending,
+/// e.g. to find out in a HardFault handler whether the fault happened while another fault handler
+/// was running.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Shcsr {
+    bits: u32,
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl Shcsr {
+    /// Creates a `Shcsr` value from raw bits
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+
+    #[inline]
+    fn bit(self, n: u32) -> bool {
+        self.bits & (1 << n) != 0
+    }
+
+    /// MemManage handler is active (MEMFAULTACT)
+    #[inline]
+    pub fn memfault_active(self) -> bool {
+        self.bit(0)
+    }
+
+    /// BusFault handler is active (BUSFAULTACT)
+    #[inline]
+    pub fn busfault_active(self) -> bool {
+        self.bit(1)
+    }
+
+    /// UsageFault handler is active (USGFAULTACT)
+    #[inline]
+    pub fn usagefault_active(self) -> bool {
+        self.bit(3)
+    }
+
+    /// SVCall handler is active (SVCALLACT)
+    #[inline]
+    pub fn svcall_active(self) -> bool {
+        self.bit(7)
+    }
+
+    /// DebugMonitor handler is active (MONITORACT)
+    #[inline]
+    pub fn monitor_active(self) -> bool {
+        self.bit(8)
+    }
+
+    /// PendSV handler is active (PENDSVACT)
+    #[inline]
+    pub fn pendsv_active(self) -> bool {
+        self.bit(10)
+    }
+
+    /// SysTick handler is active (SYSTICKACT)
+    #[inline]
+    pub fn systick_active(self) -> bool {
+        self.bit(11)
+    }
+
+    /// UsageFault is pending (USGFAULTPENDED)
+    #[inline]
+    pub fn usagefault_pending(self) -> bool {
+        self.bit(12)
+    }
+
+    /// MemManage fault is pending (MEMFAULTPENDED)
+    #[inline]
+    pub fn memfault_pending(self) -> bool {
+        self.bit(13)
+    }
+
+    /// BusFault is pending (BUSFAULTPENDED)
+    #[inline]
+    pub fn busfault_pending(self) -> bool {
+        self.bit(14)
+    }
+
+    /// SVCall is pending (SVCALLPENDED)
+    #[inline]
+    pub fn svcall_pending(self) -> bool {
+        self.bit(15)
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl SCB {
+    /// Reads the System Handler Control and State Register
+    #[inline]
+    pub fn shcsr() -> Shcsr {
+        // NOTE(unsafe) atomic read with no side effects
+        Shcsr::from_bits(unsafe { (*Self::PTR).shcsr.read() })
+    }
+}
+
+/// Selection of configurable fault handlers, see [`SCB::set_fault_handlers_enabled`]
+///
+/// The default value has all handlers disabled, which is the reset state.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FaultHandlers {
+    /// Whether the MemManage handler is enabled
+    pub mem_manage: bool,
+    /// Whether the BusFault handler is enabled
+    pub bus_fault: bool,
+    /// Whether the UsageFault handler is enabled
+    pub usage_fault: bool,
+}
+
+/// Contents of the Configurable Fault Status Register (CFSR)
+///
+/// Combines the MemManage (MMFSR), BusFault (BFSR) and UsageFault (UFSR) status registers with
+/// the fault addresses (MMFAR and BFAR) they may mark as valid. The `Debug` implementation only
+/// lists the fault bits that are set, e.g.
+/// `Cfsr { busfault: [PRECISERR @ 0x20000004], usgfault: [DIVBYZERO] }`.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Cfsr {
+    bits: u32,
+    mmfar: u32,
+    bfar: u32,
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const CFSR_MMARVALID: u32 = 1 << 7;
+#[cfg(not(any(armv6m, armv8m_base)))]
+const CFSR_BFARVALID: u32 = 1 << 15;
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl Cfsr {
+    /// Returns the reset value of the registers: no faults recorded
+    #[inline]
+    pub const fn reset() -> Self {
+        Self::from_bits(0, 0, 0)
+    }
+
+    /// Creates a `Cfsr` value from the raw contents of the CFSR, MMFAR and BFAR registers
+    #[inline]
+    pub const fn from_bits(bits: u32, mmfar: u32, bfar: u32) -> Self {
+        Self { bits, mmfar, bfar }
+    }
+
+    /// Returns the contents of the CFSR register as raw bits
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+
+    /// MemManage Fault Status Register (MMFSR) bits
+    #[inline]
+    pub const fn mmfsr(self) -> u8 {
+        self.bits as u8
+    }
+
+    /// BusFault Status Register (BFSR) bits
+    #[inline]
+    pub const fn bfsr(self) -> u8 {
+        (self.bits >> 8) as u8
+    }
+
+    /// UsageFault Status Register (UFSR) bits
+    #[inline]
+    pub const fn ufsr(self) -> u16 {
+        (self.bits >> 16) as u16
+    }
+
+    /// Address of the faulting MemManage access, if MMFSR.MMARVALID is set
+    #[inline]
+    pub fn mmfar(self) -> Option<u32> {
+        if self.bits & CFSR_MMARVALID != 0 {
+            Some(self.mmfar)
+        } else {
+            None
+        }
+    }
+
+    /// Address of the faulting bus access, if BFSR.BFARVALID is set
+    #[inline]
+    pub fn bfar(self) -> Option<u32> {
+        if self.bits & CFSR_BFARVALID != 0 {
+            Some(self.bfar)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const CFSR_MMFSR_NAMES: &[(u32, &str)] = &[
+    (1 << 0, "IACCVIOL"),
+    (1 << 1, "DACCVIOL"),
+    (1 << 3, "MUNSTKERR"),
+    (1 << 4, "MSTKERR"),
+    (1 << 5, "MLSPERR"),
+];
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const CFSR_BFSR_NAMES: &[(u32, &str)] = &[
+    (1 << 8, "IBUSERR"),
+    (1 << 9, "PRECISERR"),
+    (1 << 10, "IMPRECISERR"),
+    (1 << 11, "UNSTKERR"),
+    (1 << 12, "STKERR"),
+    (1 << 13, "LSPERR"),
+];
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const CFSR_UFSR_NAMES: &[(u32, &str)] = &[
+    (1 << 16, "UNDEFINSTR"),
+    (1 << 17, "INVSTATE"),
+    (1 << 18, "INVPC"),
+    (1 << 19, "NOCP"),
+    (1 << 20, "STKOF"),
+    (1 << 24, "UNALIGNED"),
+    (1 << 25, "DIVBYZERO"),
+];
+
+/// Formats the set bits of one of the CFSR sub-registers as a list of names
+#[cfg(not(any(armv6m, armv8m_base)))]
+struct CfsrFlags {
+    bits: u32,
+    names: &'static [(u32, &'static str)],
+    address: Option<u32>,
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl CfsrFlags {
+    fn is_empty(&self) -> bool {
+        self.names.iter().all(|&(mask, _)| self.bits & mask == 0)
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl fmt::Debug for CfsrFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        let mut first = true;
+        for &(mask, name) in self.names {
+            if self.bits & mask != 0 {
+                if !first {
+                    f.write_str(", ")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        if let Some(address) = self.address {
+            write!(f, " @ {:#010x}", address)?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl fmt::Debug for Cfsr {
+    #[allow(clippy::missing_inline_in_public_items)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Cfsr");
+        let groups = [
+            ("mmfault", CFSR_MMFSR_NAMES, self.mmfar()),
+            ("busfault", CFSR_BFSR_NAMES, self.bfar()),
+            ("usgfault", CFSR_UFSR_NAMES, None),
+        ];
+        for (name, names, address) in groups {
+            let flags = CfsrFlags {
+                bits: self.bits,
+                names,
+                address,
+            };
+            if !flags.is_empty() {
+                s.field(name, &flags);
+            }
+        }
+        s.finish()
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl SCB {
+    /// Reads the Configurable Fault Status Register, together with the MMFAR and BFAR fault
+    /// address registers
+    ///
+    /// This is meant to be called from a fault handler to find out what caused the fault.
+    #[inline]
+    pub fn cfsr() -> Cfsr {
+        // NOTE(unsafe) atomic reads with no side effects
+        unsafe {
+            let scb = &*Self::PTR;
+            Cfsr::from_bits(scb.cfsr.read(), scb.mmfar.read(), scb.bfar.read())
+        }
+    }
+
+    /// Reads the MemManage fault address register, if MMFSR.MMARVALID is set
+    ///
+    /// MMFAR holds stale data when MMARVALID is clear, in which case this returns `None`. Use
+    /// [`SCB::cfsr`] to also get the cause of the fault.
+    #[inline]
+    pub fn mmfar() -> Option<u32> {
+        // NOTE(unsafe) atomic reads with no side effects
+        unsafe {
+            let scb = &*Self::PTR;
+            if scb.cfsr.read() & CFSR_MMARVALID != 0 {
+                Some(scb.mmfar.read())
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Reads the BusFault address register, if BFSR.BFARVALID is set
+    ///
+    /// BFAR holds stale data when BFARVALID is clear, in which case this returns `None`. Use
+    /// [`SCB::cfsr`] to also get the cause of the fault.
+    #[inline]
+    pub fn bfar() -> Option<u32> {
+        // NOTE(unsafe) atomic reads with no side effects
+        unsafe {
+            let scb = &*Self::PTR;
+            if scb.cfsr.read() & CFSR_BFARVALID != 0 {
+                Some(scb.bfar.read())
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Clears the given CFSR fault status bits
+    ///
+    /// The bits are write-one-to-clear; pass [`Cfsr::bits`] to clear all the faults that were
+    /// read with [`SCB::cfsr`].
+    #[inline]
+    pub fn clear_cfsr(&mut self, bits: u32) {
+        unsafe { self.cfsr.write(bits) }
+    }
+}
+
+/// Contents of the HardFault Status Register (HFSR)
+///
+/// Tells why a HardFault was taken. If [`forced`](Hfsr::forced) is set, a configurable fault was
+/// escalated to HardFault and [`SCB::cfsr`] holds its actual cause.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Hfsr {
+    bits: u32,
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl Hfsr {
+    /// Creates a `Hfsr` value from raw bits
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.bits
+    }
+
+    /// A BusFault occurred while reading the vector table during exception processing (VECTTBL)
+    #[inline]
+    pub fn vecttbl(self) -> bool {
+        self.bits & HFSR_VECTTBL != 0
+    }
+
+    /// A configurable fault was escalated to HardFault (FORCED)
+    #[inline]
+    pub fn forced(self) -> bool {
+        self.bits & HFSR_FORCED != 0
+    }
+
+    /// A debug event occurred while halting debug was disabled (DEBUGEVT)
+    #[inline]
+    pub fn debugevt(self) -> bool {
+        self.bits & HFSR_DEBUGEVT != 0
+    }
+}
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+const HFSR_VECTTBL: u32 = 1 << 1;
+#[cfg(not(any(armv6m, armv8m_base)))]
+const HFSR_FORCED: u32 = 1 << 30;
+#[cfg(not(any(armv6m, armv8m_base)))]
+const HFSR_DEBUGEVT: u32 = 1 << 31;
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+impl SCB {
+    /// Reads the HardFault Status Register
+    #[inline]
+    pub fn hfsr() -> Hfsr {
+        // NOTE(unsafe) atomic read with no side effects
+        Hfsr::from_bits(unsafe { (*Self::PTR).hfsr.read() })
+    }
+
+    /// Clears the HardFault status bits
+    ///
+    /// The bits are sticky and write-one-to-clear, so they must be cleared before the next
+    /// HardFault can be told apart from this one.
+    #[inline]
+    pub fn clear_hfsr(&mut self) {
+        unsafe { self.hfsr.write(HFSR_VECTTBL | HFSR_FORCED | HFSR_DEBUGEVT) }
+    }
+}
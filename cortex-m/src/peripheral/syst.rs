@@ -1,5 +1,7 @@
 //! SysTick: System Timer
 
+use core::sync::atomic::{AtomicU32, Ordering};
+
 use volatile_register::{RO, RW};
 
 use crate::peripheral::SYST;
@@ -26,6 +28,23 @@ pub enum SystClkSource {
     External,
 }
 
+/// Possible error values returned on [SYST::configure_1khz] and [SYST::try_set_reload].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum SystError {
+    /// The required reload value doesn't fit in the 24-bit reload register.
+    ReloadOutOfRange,
+}
+
+/// Reason why [`SYST::wfi_for`] returned
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WakeReason {
+    /// The counter wrapped: the requested number of ticks elapsed
+    Timeout,
+    /// Another interrupt, or some other wakeup event, woke the processor up before the timeout
+    Interrupt,
+}
+
 const SYST_COUNTER_MASK: u32 = 0x00ff_ffff;
 
 const SYST_CSR_ENABLE: u32 = 1 << 0;
@@ -45,6 +64,30 @@ impl SYST {
         unsafe { self.cvr.write(0) }
     }
 
+    /// Configures the counter to wrap, and fire the SysTick interrupt, once every millisecond
+    ///
+    /// `sysclk_hz` is the frequency of the core clock in Hz, which is selected as clock source.
+    /// The counter is (re)started and the SysTick interrupt is enabled.
+    ///
+    /// Returns [`SystError::ReloadOutOfRange`] without touching the timer if a 1 kHz tick can't
+    /// be produced from `sysclk_hz`, i.e. if the required reload value is not within
+    /// `1..=0x00ffffff`.
+    #[inline]
+    pub fn configure_1khz(&mut self, sysclk_hz: u32) -> Result<(), SystError> {
+        let ticks = sysclk_hz / 1_000;
+        if ticks < 2 || ticks - 1 > SYST_COUNTER_MASK {
+            return Err(SystError::ReloadOutOfRange);
+        }
+
+        self.set_clock_source(SystClkSource::Core);
+        self.set_reload(ticks - 1);
+        self.clear_current();
+        self.enable_interrupt();
+        self.enable_counter();
+
+        Ok(())
+    }
+
     /// Disables counter
     #[inline]
     pub fn disable_counter(&mut self) {
@@ -173,6 +216,54 @@ impl SYST {
         }
     }
 
+    /// Sleeps with `wfi` for at most `ticks` SysTick clock ticks
+    ///
+    /// Programs the counter to wrap after `ticks` ticks, enables the SysTick interrupt and the
+    /// counter, and executes [`wfi`](crate::asm::wfi). On wakeup the counter is stopped, the
+    /// previous state of the SysTick interrupt is restored, and COUNTFLAG tells whether the
+    /// timeout expired.
+    ///
+    /// The SysTick handler runs on timeout, unless interrupts are masked with PRIMASK (e.g. inside
+    /// [`interrupt::free`](crate::interrupt::free)). `wfi` still wakes up in that case, which makes
+    /// it possible to check for work and go to sleep without a race.
+    ///
+    /// *NOTE* The processor may wake up for other reasons than an interrupt (e.g. a debug event),
+    /// which are reported as [`WakeReason::Interrupt`]. Callers must re-check their wakeup
+    /// conditions instead of assuming that some interrupt handler ran.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ticks` is not in the range `2..=0x0100_0000`.
+    #[cfg(cortex_m)]
+    #[inline]
+    pub fn wfi_for(&mut self, ticks: u32) -> WakeReason {
+        assert!((2..=SYST_COUNTER_MASK + 1).contains(&ticks));
+
+        let interrupt_enabled = self.is_interrupt_enabled();
+
+        self.disable_counter();
+        self.set_reload(ticks - 1);
+        // NOTE this also clears COUNTFLAG
+        self.clear_current();
+        self.enable_interrupt();
+        self.enable_counter();
+
+        crate::asm::wfi();
+
+        let reason = if self.has_wrapped() {
+            WakeReason::Timeout
+        } else {
+            WakeReason::Interrupt
+        };
+
+        self.disable_counter();
+        if !interrupt_enabled {
+            self.disable_interrupt();
+        }
+
+        reason
+    }
+
     /// Sets reload value
     ///
     /// Valid values are between `1` and `0x00ffffff`.
@@ -182,4 +273,113 @@ impl SYST {
     pub fn set_reload(&mut self, value: u32) {
         unsafe { self.rvr.write(value) }
     }
+
+    /// Sets reload value, checking that it fits in the 24-bit reload register
+    ///
+    /// Returns [`SystError::ReloadOutOfRange`] without touching the timer if `value` is larger
+    /// than `0x00ffffff`; [`SYST::set_reload`] would silently drop the upper bits instead. Prefer
+    /// this function when the reload value is computed, e.g. from a clock frequency.
+    #[inline]
+    pub fn try_set_reload(&mut self, value: u32) -> Result<(), SystError> {
+        if value > SYST_COUNTER_MASK {
+            return Err(SystError::ReloadOutOfRange);
+        }
+
+        self.set_reload(value);
+
+        Ok(())
+    }
+}
+
+/// Extends the 24-bit SysTick counter to a monotonic 64-bit tick count
+///
+/// The counter combines a software count of SysTick wraps with the current value of the
+/// hardware counter. The wraps are counted by the SysTick handler, which must call
+/// [`MonotonicCounter::on_wrap`]:
+///
+/// ```no_run
+/// use cortex_m::peripheral::syst::MonotonicCounter;
+///
+/// static TICKS: MonotonicCounter = MonotonicCounter::new();
+///
+/// // #[exception]
+/// fn SysTick() {
+///     TICKS.on_wrap();
+/// }
+///
+/// // anywhere else
+/// let now = TICKS.now();
+/// ```
+///
+/// The SysTick counter and interrupt must be enabled (see [`SYST::enable_counter`] and
+/// [`SYST::enable_interrupt`]), and the reload value must not change while the counter is in
+/// use.
+pub struct MonotonicCounter {
+    wraps: AtomicU32,
+}
+
+impl MonotonicCounter {
+    /// Creates a counter starting at 0 wraps
+    #[inline]
+    pub const fn new() -> Self {
+        MonotonicCounter {
+            wraps: AtomicU32::new(0),
+        }
+    }
+
+    /// Counts one wrap of the SysTick counter; must be called from the SysTick handler, and only
+    /// from there
+    #[inline]
+    pub fn on_wrap(&self) {
+        // NOTE a load followed by a store, as ARMv6-M has no atomic read-modify-write; the
+        // SysTick handler is the only writer and can't preempt itself
+        let wraps = self.wraps.load(Ordering::Relaxed);
+        self.wraps.store(wraps.wrapping_add(1), Ordering::Relaxed);
+    }
+
+    /// Returns the number of wraps counted so far by [`MonotonicCounter::on_wrap`]
+    #[inline]
+    pub fn wraps(&self) -> u32 {
+        self.wraps.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of SysTick clock ticks counted so far
+    ///
+    /// Reading the wrap count and the hardware counter is not atomic: the counter can wrap in
+    /// between. This retries if the SysTick handler ran during the reads, and accounts for a wrap
+    /// whose interrupt is still pending (e.g. because `now` is called from a critical section or
+    /// a higher priority handler), so the result never goes backwards.
+    ///
+    /// *NOTE* If the SysTick interrupt stays pending for more than one counter period, the
+    /// wraps that happen in the meantime are lost. The count itself wraps after 2^32 counter
+    /// periods.
+    #[inline]
+    pub fn now(&self) -> u64 {
+        let period = u64::from(SYST::get_reload()) + 1;
+
+        loop {
+            let before = self.wraps.load(Ordering::Relaxed);
+            let mut wraps = u64::from(before);
+            let mut current = SYST::get_current();
+
+            if crate::peripheral::SCB::is_pendst_pending() {
+                // The counter wrapped but `on_wrap` hasn't run yet: read the counter again, to be
+                // sure the value is from after the wrap
+                current = SYST::get_current();
+                wraps += 1;
+            }
+
+            // Retry if the SysTick handler ran in the meantime
+            if self.wraps.load(Ordering::Relaxed) == before {
+                return wraps * period + (period - 1 - u64::from(current));
+            }
+        }
+    }
+}
+
+impl Default for MonotonicCounter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -88,6 +88,36 @@ fn itm() {
     assert_eq!(address(&itm.tcr), 0xE000_0E80);
     assert_eq!(address(&itm.lar), 0xE000_0FB0);
     assert_eq!(address(&itm.lsr), 0xE000_0FB4);
+
+    assert_eq!(crate::itm::stim_ptr(3) as usize, 0xE000_000C);
+}
+
+#[test]
+fn itm_frame_buffer() {
+    use crate::itm::FrameBuffer;
+    use crate::peripheral::itm::Stim;
+
+    // A stimulus port backed by memory. It stays ready (bit 0 set) as long as the first byte of
+    // every write is odd.
+    let mut register = 1u32;
+    let register: *mut u32 = &mut register;
+    let port = unsafe { &mut *(register as *mut Stim) };
+
+    let mut buffer = FrameBuffer::<4>::new(port);
+    // spans two flushes; the last 4 bytes stay buffered
+    buffer.write(&[1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23]);
+    assert_eq!(
+        unsafe { register.read_volatile() },
+        u32::from_le_bytes([9, 11, 13, 15])
+    );
+
+    // a partial buffer is flushed when dropped
+    buffer.write(&[25]);
+    drop(buffer);
+    assert_eq!(
+        unsafe { register.read_volatile() },
+        u32::from_le_bytes([25, 19, 21, 23])
+    );
 }
 
 #[test]
@@ -107,6 +137,23 @@ fn mpu() {
     assert_eq!(address(&mpu.rasr_a3), 0xE000EDB8);
 }
 
+#[test]
+fn mpu_region_attributes() {
+    use crate::peripheral::mpu::{AccessPermission, RegionAttributes};
+
+    assert_eq!(RegionAttributes::normal_wb_wa().bits(), 0x000B_0000);
+    assert_eq!(RegionAttributes::normal_non_cacheable().bits(), 0x0008_0000);
+    assert_eq!(RegionAttributes::device_shared().bits(), 0x0005_0000);
+    assert_eq!(RegionAttributes::strongly_ordered().bits(), 0);
+
+    let attrs = RegionAttributes::normal_wb_wa()
+        .shareable()
+        .access(AccessPermission::ReadOnly)
+        .execute_never()
+        .subregion_disable(0b1000_0001);
+    assert_eq!(attrs.bits(), 0x160F_8100);
+}
+
 #[test]
 fn nvic() {
     let nvic = unsafe { &*crate::peripheral::NVIC::PTR };
@@ -121,6 +168,72 @@ fn nvic() {
     assert_eq!(address(&nvic.stir), 0xE000EF00);
 }
 
+#[test]
+fn nvic_priority_encoding() {
+    use crate::peripheral::nvic::{decode_priority, encode_priority};
+    use crate::peripheral::scb::PriorityGrouping;
+
+    // 4 implemented bits, all of them group priority
+    assert_eq!(
+        encode_priority(PriorityGrouping::Group4Sub4, 4, 0xA, 0),
+        0xA0
+    );
+    assert_eq!(
+        decode_priority(PriorityGrouping::Group4Sub4, 4, 0xA0),
+        (0xA, 0)
+    );
+
+    // 4 implemented bits split 2/2
+    assert_eq!(
+        encode_priority(PriorityGrouping::Group2Sub6, 4, 0b10, 0b01),
+        0b1001_0000
+    );
+    assert_eq!(
+        decode_priority(PriorityGrouping::Group2Sub6, 4, 0b1001_0000),
+        (0b10, 0b01)
+    );
+
+    // more group bits than implemented bits
+    assert_eq!(
+        encode_priority(PriorityGrouping::Group7Sub1, 3, 0x7f, 1),
+        0b1110_0000
+    );
+    assert_eq!(
+        decode_priority(PriorityGrouping::Group7Sub1, 3, 0xff),
+        (0b111, 0)
+    );
+
+    // no group priority
+    assert_eq!(
+        encode_priority(PriorityGrouping::Group0Sub8, 8, 3, 0x42),
+        0x42
+    );
+    assert_eq!(
+        decode_priority(PriorityGrouping::Group0Sub8, 8, 0x42),
+        (0, 0x42)
+    );
+
+    // out of range fields saturate
+    assert_eq!(encode_priority(PriorityGrouping::Group2Sub6, 4, 9, 9), 0xf0);
+}
+
+#[test]
+fn priority_grouping_fits_priority_bits() {
+    use crate::peripheral::scb::PriorityGrouping;
+
+    // all the implemented bits are group priority bits
+    assert!(PriorityGrouping::Group4Sub4.fits_priority_bits(4));
+    assert!(PriorityGrouping::Group3Sub5.fits_priority_bits(4));
+    assert!(PriorityGrouping::Group0Sub8.fits_priority_bits(4));
+    // more group priority bits than implemented
+    assert!(!PriorityGrouping::Group5Sub3.fits_priority_bits(4));
+    assert!(!PriorityGrouping::Group6Sub2.fits_priority_bits(3));
+    // the reset value is always accepted
+    assert!(PriorityGrouping::Group7Sub1.fits_priority_bits(3));
+    // with 8 bits every grouping fits
+    assert!(PriorityGrouping::Group6Sub2.fits_priority_bits(8));
+}
+
 #[test]
 fn scb() {
     let scb = unsafe { &*crate::peripheral::SCB::PTR };
@@ -168,3 +281,151 @@ fn tpiu() {
 fn address<T>(r: *const T) -> usize {
     r as usize
 }
+
+#[test]
+fn scb_cfsr_debug() {
+    extern crate std;
+    use crate::peripheral::scb::Cfsr;
+    use std::format;
+
+    assert_eq!(format!("{:?}", Cfsr::reset()), "Cfsr");
+
+    let cfsr = Cfsr::from_bits(1 << 25 | 1 << 24 | 1 << 15 | 1 << 9, 0, 0x2000_0004);
+    assert_eq!(cfsr.bfar(), Some(0x2000_0004));
+    assert_eq!(cfsr.mmfar(), None);
+    assert_eq!(
+        format!("{:?}", cfsr),
+        "Cfsr { busfault: [PRECISERR @ 0x20000004], usgfault: [UNALIGNED, DIVBYZERO] }"
+    );
+
+    let cfsr = Cfsr::from_bits(1 << 7 | 1 << 1, 0x1000, 0);
+    assert_eq!(
+        format!("{:?}", cfsr),
+        "Cfsr { mmfault: [DACCVIOL @ 0x00001000] }"
+    );
+}
+
+#[test]
+fn nvic_priority_table() {
+    use crate::peripheral::nvic::PriorityTable;
+
+    #[derive(Clone, Copy)]
+    struct Irq(u16);
+    unsafe impl crate::interrupt::InterruptNumber for Irq {
+        fn number(self) -> u16 {
+            self.0
+        }
+    }
+
+    const TABLE: PriorityTable<6> = PriorityTable::new([0x10, 0x20, 0x30, 0x40, 0x50, 0x60]);
+
+    let mut table = TABLE;
+    assert_eq!(table.get(Irq(2)), 0x30);
+    table.set(Irq(2), 0xf0);
+    assert_eq!(table.get(Irq(2)), 0xf0);
+    assert_eq!(table.0, [0x10, 0x20, 0xf0, 0x40, 0x50, 0x60]);
+}
+
+#[test]
+fn nvic_interrupt_state() {
+    use crate::peripheral::nvic::InterruptState;
+
+    assert!(!InterruptState::Inactive.is_active() && !InterruptState::Inactive.is_pending());
+    assert!(!InterruptState::Pending.is_active() && InterruptState::Pending.is_pending());
+    assert!(InterruptState::Active.is_active() && !InterruptState::Active.is_pending());
+    assert!(
+        InterruptState::ActivePending.is_active() && InterruptState::ActivePending.is_pending()
+    );
+}
+
+// All public enums are plain values: `Clone`, `Copy`, `Debug`, `Eq` and `PartialEq`
+fn assert_value_enum<T: Clone + Copy + core::fmt::Debug + Eq + PartialEq>(a: T, b: T) {
+    let copy = a;
+    assert_eq!(copy, a.clone());
+    assert_ne!(a, b);
+}
+
+#[test]
+fn value_enums() {
+    use crate::peripheral::cpuid::CsselrCacheType;
+    use crate::peripheral::dwt::{AccessType, DwtError, EmitOption, SyncTap};
+    use crate::peripheral::itm::{GlobalTimestampOptions, LocalTimestampOptions, TimestampClkSrc};
+    use crate::peripheral::mpu::AccessPermission;
+    use crate::peripheral::nvic::InterruptState;
+    use crate::peripheral::scb::{Exception, PriorityGrouping, SystemHandler, VectActive};
+    use crate::peripheral::syst::{SystClkSource, SystError, WakeReason};
+    use crate::peripheral::tpiu::TraceProtocol;
+    use crate::trace::TraceError;
+
+    assert_value_enum(CsselrCacheType::DataOrUnified, CsselrCacheType::Instruction);
+    assert_value_enum(AccessType::ReadOnly, AccessType::ReadWrite);
+    assert_value_enum(DwtError::InvalidFunction, DwtError::InvalidComparator);
+    assert_value_enum(EmitOption::Data, EmitOption::Address);
+    assert_value_enum(SyncTap::Disabled, SyncTap::Bit24);
+    assert_value_enum(
+        GlobalTimestampOptions::Disabled,
+        GlobalTimestampOptions::Every128Cycles,
+    );
+    assert_value_enum(
+        LocalTimestampOptions::Disabled,
+        LocalTimestampOptions::Enabled,
+    );
+    assert_value_enum(TimestampClkSrc::SystemClock, TimestampClkSrc::AsyncTPIU);
+    assert_value_enum(
+        AccessPermission::NoAccess,
+        AccessPermission::PrivilegedReadWrite,
+    );
+    assert_value_enum(InterruptState::Pending, InterruptState::ActivePending);
+    assert_value_enum(Exception::HardFault, Exception::NonMaskableInt);
+    assert_value_enum(PriorityGrouping::Group7Sub1, PriorityGrouping::Group5Sub3);
+    assert_value_enum(SystemHandler::PendSV, SystemHandler::SysTick);
+    assert_value_enum(
+        VectActive::ThreadMode,
+        VectActive::Exception(Exception::HardFault),
+    );
+    assert_value_enum(SystClkSource::Core, SystClkSource::External);
+    assert_eq!(SystError::ReloadOutOfRange, SystError::ReloadOutOfRange);
+    assert_value_enum(TraceProtocol::Parallel, TraceProtocol::AsyncSWONRZ);
+    assert_value_enum(WakeReason::Timeout, WakeReason::Interrupt);
+    assert_value_enum(TraceError::ZeroBaudRate, TraceError::BaudRateTooHigh);
+    assert_value_enum(TraceError::BaudRateTooHigh, TraceError::BaudRateTooLow);
+}
+
+#[test]
+fn syst_monotonic_counter_wraps() {
+    use crate::peripheral::syst::MonotonicCounter;
+
+    let counter = MonotonicCounter::new();
+    counter.on_wrap();
+    counter.on_wrap();
+    assert_eq!(counter.wraps(), 2);
+}
+
+#[test]
+fn scb_shcsr() {
+    use crate::peripheral::scb::Shcsr;
+
+    let shcsr = Shcsr::from_bits(1 << 0 | 1 << 11 | 1 << 14);
+    assert!(shcsr.memfault_active());
+    assert!(shcsr.systick_active());
+    assert!(shcsr.busfault_pending());
+    assert!(!shcsr.busfault_active());
+    assert!(!shcsr.usagefault_active());
+    assert!(!shcsr.memfault_pending());
+    assert_eq!(shcsr.bits(), 0x4801);
+}
+
+#[test]
+fn scb_hfsr() {
+    use crate::peripheral::scb::Hfsr;
+
+    let hfsr = Hfsr::from_bits(1 << 30);
+    assert!(hfsr.forced());
+    assert!(!hfsr.vecttbl());
+    assert!(!hfsr.debugevt());
+
+    let hfsr = Hfsr::from_bits(1 << 31 | 1 << 1);
+    assert!(hfsr.vecttbl());
+    assert!(hfsr.debugevt());
+    assert!(!hfsr.forced());
+}
@@ -2,6 +2,7 @@
 
 #[cfg(cortex_m)]
 use core::arch::asm;
+use core::fmt;
 
 /// Application Program Status Register
 #[derive(Clone, Copy, Debug)]
@@ -10,9 +11,15 @@ pub struct Apsr {
 }
 
 impl Apsr {
+    /// Creates an `Apsr` value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
-    pub fn bits(self) -> u32 {
+    pub const fn bits(self) -> u32 {
         self.bits
     }
 
@@ -45,6 +52,79 @@ impl Apsr {
     pub fn n(self) -> bool {
         self.bits & (1 << 31) == (1 << 31)
     }
+
+    /// Evaluates the condition code `cond` against the N, Z, C and V flags
+    ///
+    /// Returns `true` if an instruction with condition `cond` would execute.
+    #[inline]
+    pub fn condition_holds(self, cond: Condition) -> bool {
+        let (n, z, c, v) = (self.n(), self.z(), self.c(), self.v());
+        match cond {
+            Condition::Eq => z,
+            Condition::Ne => !z,
+            Condition::Cs => c,
+            Condition::Cc => !c,
+            Condition::Mi => n,
+            Condition::Pl => !n,
+            Condition::Vs => v,
+            Condition::Vc => !v,
+            Condition::Hi => c && !z,
+            Condition::Ls => !c || z,
+            Condition::Ge => n == v,
+            Condition::Lt => n != v,
+            Condition::Gt => !z && n == v,
+            Condition::Le => z || n != v,
+        }
+    }
+}
+
+/// Condition codes of conditional instructions, see [`Apsr::condition_holds`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Condition {
+    /// Equal (Z set)
+    Eq,
+    /// Not equal (Z clear)
+    Ne,
+    /// Carry set / unsigned higher or same (C set)
+    Cs,
+    /// Carry clear / unsigned lower (C clear)
+    Cc,
+    /// Minus, negative (N set)
+    Mi,
+    /// Plus, positive or zero (N clear)
+    Pl,
+    /// Overflow (V set)
+    Vs,
+    /// No overflow (V clear)
+    Vc,
+    /// Unsigned higher (C set and Z clear)
+    Hi,
+    /// Unsigned lower or same (C clear or Z set)
+    Ls,
+    /// Signed greater than or equal (N equals V)
+    Ge,
+    /// Signed less than (N differs from V)
+    Lt,
+    /// Signed greater than (Z clear and N equals V)
+    Gt,
+    /// Signed less than or equal (Z set or N differs from V)
+    Le,
+}
+
+impl fmt::Display for Apsr {
+    /// Formats the register flags, e.g. `APSR{N=0 Z=1 C=1 V=0 Q=0}`
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "APSR{{N={} Z={} C={} V={} Q={}}}",
+            u8::from(self.n()),
+            u8::from(self.z()),
+            u8::from(self.c()),
+            u8::from(self.v()),
+            u8::from(self.q()),
+        )
+    }
 }
 
 /// Reads the CPU register
@@ -55,3 +135,11 @@ pub fn read() -> Apsr {
     unsafe { asm!("mrs {}, APSR", out(reg) bits, options(nomem, nostack, preserves_flags)) };
     Apsr { bits }
 }
+
+#[cfg(cortex_m)]
+impl super::CoreRegister for Apsr {
+    #[inline]
+    fn read() -> Self {
+        read()
+    }
+}
@@ -8,6 +8,12 @@ use core::arch::asm;
 /// - `basepri != 0` AND `basepri::read() == 0`, OR
 /// - `basepri != 0` AND `basepri < basepri::read()`
 ///
+/// That is, the write can only raise the masking threshold (mask more exceptions), never lower
+/// it. This is the primitive to enter a BASEPRI-based critical section: unlike
+/// [`basepri::write`](super::basepri::write), which sets the register unconditionally, it can't
+/// accidentally unmask exceptions masked by an enclosing critical section. This is also why this
+/// function is safe.
+///
 /// **IMPORTANT** If you are using a Cortex-M7 device with revision r0p1 you MUST enable the
 /// `cm7-r0p1` Cargo feature or this function WILL misbehave.
 #[cfg(cortex_m)]
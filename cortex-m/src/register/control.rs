@@ -2,6 +2,7 @@
 
 #[cfg(cortex_m)]
 use core::arch::asm;
+use core::fmt;
 #[cfg(cortex_m)]
 use core::sync::atomic::{compiler_fence, Ordering};
 
@@ -12,15 +13,24 @@ pub struct Control {
 }
 
 impl Control {
+    /// Returns the reset value of the register: privileged thread mode, MSP as the active stack
+    /// pointer and no active floating-point context.
+    ///
+    /// This can be used to compute the value to [`write`] without reading the register first.
+    #[inline]
+    pub const fn thread_mode_default() -> Self {
+        Self { bits: 0 }
+    }
+
     /// Creates a `Control` value from raw bits.
     #[inline]
-    pub fn from_bits(bits: u32) -> Self {
+    pub const fn from_bits(bits: u32) -> Self {
         Self { bits }
     }
 
     /// Returns the contents of the register as raw bits
     #[inline]
-    pub fn bits(self) -> u32 {
+    pub const fn bits(self) -> u32 {
         self.bits
     }
 
@@ -65,6 +75,10 @@ impl Control {
     }
 
     /// Whether context floating-point is currently active
+    ///
+    /// *NOTE* Only available on targets with an FPU.
+    // NOTE(native) is for documentation purposes
+    #[cfg(any(has_fpu, native))]
     #[inline]
     pub fn fpca(self) -> Fpca {
         if self.bits & (1 << 2) == (1 << 2) {
@@ -75,6 +89,9 @@ impl Control {
     }
 
     /// Sets the FPCA value.
+    ///
+    /// *NOTE* Only available on targets with an FPU.
+    #[cfg(any(has_fpu, native))]
     #[inline]
     pub fn set_fpca(&mut self, fpca: Fpca) {
         let mask = 1 << 2;
@@ -85,6 +102,28 @@ impl Control {
     }
 }
 
+impl fmt::Display for Control {
+    /// Formats the register fields, e.g. `CONTROL{nPRIV=0 SPSEL=1 FPCA=0}`
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CONTROL{{nPRIV={} SPSEL={} FPCA={}}}",
+            self.bits & 1,
+            (self.bits >> 1) & 1,
+            (self.bits >> 2) & 1,
+        )
+    }
+}
+
+impl Default for Control {
+    /// Returns the reset value of the register, see [`Control::thread_mode_default`].
+    #[inline]
+    fn default() -> Self {
+        Self::thread_mode_default()
+    }
+}
+
 /// Thread mode privilege level
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Npriv {
@@ -108,6 +147,14 @@ impl Npriv {
     }
 }
 
+impl Default for Npriv {
+    /// Returns the reset value, `Privileged`
+    #[inline]
+    fn default() -> Self {
+        Npriv::Privileged
+    }
+}
+
 /// Currently active stack pointer
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Spsel {
@@ -131,6 +178,14 @@ impl Spsel {
     }
 }
 
+impl Default for Spsel {
+    /// Returns the reset value, `Msp`
+    #[inline]
+    fn default() -> Self {
+        Spsel::Msp
+    }
+}
+
 /// Whether context floating-point is currently active
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Fpca {
@@ -154,7 +209,107 @@ impl Fpca {
     }
 }
 
+impl Default for Fpca {
+    /// Returns the reset value, `NotActive`
+    #[inline]
+    fn default() -> Self {
+        Fpca::NotActive
+    }
+}
+
+/// Builder for [`Control`] values
+///
+/// Starts from the reset value ([`Control::thread_mode_default`]). Use [`build`] to get the
+/// resulting `Control` value, or [`try_write`] to write it to the register after checking that
+/// the current execution mode allows the change.
+///
+/// [`build`]: ControlBuilder::build
+/// [`try_write`]: ControlBuilder::try_write
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ControlBuilder {
+    control: Control,
+}
+
+impl ControlBuilder {
+    /// Creates a builder starting from the reset value of the register
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            control: Control::thread_mode_default(),
+        }
+    }
+
+    /// Thread mode runs unprivileged (nPRIV = 1)
+    #[inline]
+    pub fn unprivileged(mut self) -> Self {
+        self.control.set_npriv(Npriv::Unprivileged);
+        self
+    }
+
+    /// Thread mode runs privileged (nPRIV = 0)
+    #[inline]
+    pub fn privileged(mut self) -> Self {
+        self.control.set_npriv(Npriv::Privileged);
+        self
+    }
+
+    /// Thread mode uses the process stack (SPSEL = 1)
+    #[inline]
+    pub fn use_psp(mut self) -> Self {
+        self.control.set_spsel(Spsel::Psp);
+        self
+    }
+
+    /// Thread mode uses the main stack (SPSEL = 0)
+    #[inline]
+    pub fn use_msp(mut self) -> Self {
+        self.control.set_spsel(Spsel::Msp);
+        self
+    }
+
+    /// Returns the configured `Control` value
+    #[inline]
+    pub fn build(self) -> Control {
+        self.control
+    }
+
+    /// Writes the configured value to the CPU register, if the current execution mode allows it
+    ///
+    /// The FPCA bit is left as it currently is, so an active floating-point context is not lost.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`write_checked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as [`write`]: changing the active stack pointer or dropping privileges can break
+    /// assumptions the code following this call relies on.
+    #[cfg(cortex_m)]
+    #[inline]
+    pub unsafe fn try_write(self) -> Result<(), ControlError> {
+        let fpca = 1 << 2;
+        write_checked(Control::from_bits(
+            (self.control.bits() & !fpca) | (read().bits() & fpca),
+        ))
+    }
+}
+
+/// Possible error values returned by [`write_checked`] and [`ControlBuilder::try_write`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum ControlError {
+    /// SPSEL can't be changed in Handler mode
+    HandlerMode,
+    /// CONTROL can't be written from unprivileged Thread mode
+    Unprivileged,
+}
+
 /// Reads the CPU register
+///
+/// *NOTE* Every call executes an `mrs` instruction; the value is not cached. If the value is
+/// known not to change, read it once and reuse it, or start from
+/// [`Control::thread_mode_default`] when the register is still in its reset state.
 #[cfg(cortex_m)]
 #[inline]
 pub fn read() -> Control {
@@ -163,6 +318,39 @@ pub fn read() -> Control {
     Control { bits }
 }
 
+/// Writes to the CPU register, if the current execution mode allows the change
+///
+/// Unlike [`write`], this detects writes the hardware would silently ignore, which makes stack
+/// switching mistakes diagnosable.
+///
+/// # Errors
+///
+/// - [`ControlError::Unprivileged`] if running in unprivileged Thread mode, where writes to
+///   CONTROL are ignored.
+/// - [`ControlError::HandlerMode`] if running in Handler mode and the SPSEL value would change;
+///   Handler mode always uses the main stack and such writes are ignored.
+///
+/// # Safety
+///
+/// Same as [`write`].
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn write_checked(control: Control) -> Result<(), ControlError> {
+    let current = read();
+
+    if super::in_handler_mode() {
+        if current.spsel() != control.spsel() {
+            return Err(ControlError::HandlerMode);
+        }
+    } else if current.npriv() == Npriv::Unprivileged {
+        return Err(ControlError::Unprivileged);
+    }
+
+    write(control);
+
+    Ok(())
+}
+
 /// Writes to the CPU register.
 #[cfg(cortex_m)]
 #[inline]
@@ -171,6 +359,8 @@ pub unsafe fn write(control: Control) {
 
     // ISB is required after writing to CONTROL,
     // per ARM architectural requirements (see Application Note 321).
+    // It's in the same asm! block as the write, rather than a call to
+    // `asm::barrier_after_control_write`, so nothing can be scheduled in between.
     asm!(
         "msr CONTROL, {}",
         "isb",
@@ -181,3 +371,19 @@ pub unsafe fn write(control: Control) {
     // Ensure memory accesses are not reordered around the CONTROL update.
     compiler_fence(Ordering::SeqCst);
 }
+
+#[cfg(cortex_m)]
+impl super::CoreRegister for Control {
+    #[inline]
+    fn read() -> Self {
+        read()
+    }
+}
+
+#[cfg(cortex_m)]
+impl super::WritableCoreRegister for Control {
+    #[inline]
+    unsafe fn write(self) {
+        write(self)
+    }
+}
@@ -38,3 +38,11 @@ pub fn read() -> Faultmask {
         Faultmask::Active
     }
 }
+
+#[cfg(cortex_m)]
+impl super::CoreRegister for Faultmask {
+    #[inline]
+    fn read() -> Self {
+        read()
+    }
+}
@@ -1,6 +1,8 @@
 //! Floating-point Status Control Register
 
+#[cfg(cortex_m)]
 use core::arch::asm;
+use core::fmt;
 
 /// Floating-point Status Control Register
 #[derive(Clone, Copy, Debug)]
@@ -11,13 +13,13 @@ pub struct Fpscr {
 impl Fpscr {
     /// Creates a `Fspcr` value from raw bits.
     #[inline]
-    pub fn from_bits(bits: u32) -> Self {
+    pub const fn from_bits(bits: u32) -> Self {
         Self { bits }
     }
 
     /// Returns the contents of the register as raw bits
     #[inline]
-    pub fn bits(self) -> u32 {
+    pub const fn bits(self) -> u32 {
         self.bits
     }
 
@@ -253,6 +255,33 @@ impl Fpscr {
     }
 }
 
+impl fmt::Display for Fpscr {
+    /// Formats the register fields, e.g.
+    /// `FPSCR{N=0 Z=0 C=0 V=0 AHP=0 DN=0 FZ=0 RMode=0 IDC=0 IXC=0 UFC=0 OFC=0 DZC=0 IOC=0}`
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FPSCR{{N={} Z={} C={} V={} AHP={} DN={} FZ={} RMode={} \
+             IDC={} IXC={} UFC={} OFC={} DZC={} IOC={}}}",
+            u8::from(self.n()),
+            u8::from(self.z()),
+            u8::from(self.c()),
+            u8::from(self.v()),
+            u8::from(self.ahp()),
+            u8::from(self.dn()),
+            u8::from(self.fz()),
+            (self.bits >> 22) & 0b11,
+            u8::from(self.idc()),
+            u8::from(self.ixc()),
+            u8::from(self.ufc()),
+            u8::from(self.ofc()),
+            u8::from(self.dzc()),
+            u8::from(self.ioc()),
+        )
+    }
+}
+
 /// Rounding mode
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RMode {
@@ -293,6 +322,7 @@ impl RMode {
 }
 
 /// Read the FPSCR register
+#[cfg(cortex_m)]
 #[inline]
 pub fn read() -> Fpscr {
     let r;
@@ -301,8 +331,25 @@ pub fn read() -> Fpscr {
 }
 
 /// Set the value of the FPSCR register
+#[cfg(cortex_m)]
 #[inline]
 pub unsafe fn write(fpscr: Fpscr) {
     let fpscr = fpscr.bits();
     asm!("vmsr fpscr, {}", in(reg) fpscr, options(nomem, nostack));
 }
+
+#[cfg(cortex_m)]
+impl super::CoreRegister for Fpscr {
+    #[inline]
+    fn read() -> Self {
+        read()
+    }
+}
+
+#[cfg(cortex_m)]
+impl super::WritableCoreRegister for Fpscr {
+    #[inline]
+    unsafe fn write(self) {
+        write(self)
+    }
+}
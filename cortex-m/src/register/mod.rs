@@ -41,7 +41,8 @@ pub mod control;
 #[cfg(all(not(armv6m), not(armv8m_base)))]
 pub mod faultmask;
 
-#[cfg(has_fpu)]
+// NOTE(native) is for documentation purposes
+#[cfg(any(has_fpu, native))]
 pub mod fpscr;
 
 pub mod msp;
@@ -61,3 +62,235 @@ pub mod apsr;
 pub mod lr;
 
 pub mod pc;
+
+mod snapshot;
+
+pub use self::snapshot::{snapshot, CpuSnapshot};
+
+#[cfg(not(any(armv6m, armv8m_base)))]
+pub use self::snapshot::dump_to_itm;
+
+#[cfg(cortex_m)]
+use core::arch::asm;
+
+/// A core register whose contents are represented by the implementing type
+///
+/// This is implemented by the register value types of this module ([`apsr::Apsr`],
+/// [`control::Control`], [`primask::Primask`], ...) so that generic code can read any of them.
+/// [`CoreRegister::read`] is the same as the `read` function of the register's module.
+pub trait CoreRegister: Sized {
+    /// Reads the CPU register
+    fn read() -> Self;
+}
+
+/// A [`CoreRegister`] that can also be written
+pub trait WritableCoreRegister: CoreRegister {
+    /// Writes `self` to the CPU register
+    ///
+    /// This is the same as the `write` function of the register's module.
+    ///
+    /// # Safety
+    ///
+    /// See the `write` function of the register's module.
+    unsafe fn write(self);
+}
+
+/// Moves Thread mode execution onto the process stack and jumps to `entry`
+///
+/// Sets PSP to `initial_psp`, selects PSP as the active stack pointer (CONTROL.SPSEL = 1),
+/// issues the ISB required after writing CONTROL and branches to `entry`. This is the standard
+/// way for an RTOS to start running threads on the process stack, while exception handlers keep
+/// using the main stack.
+///
+/// All of this happens in a single assembly block, without touching the stack in between. The
+/// stack frame of the caller is abandoned, since it lives on the main stack, which is why this
+/// function jumps to `entry` instead of returning.
+///
+/// # Safety
+///
+/// - Must be called from privileged Thread mode; SPSEL can't be changed from Handler mode.
+/// - `initial_psp` must be the 8-byte aligned top of a stack region large enough for `entry`.
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn switch_to_psp(initial_psp: u32, entry: extern "C" fn() -> !) -> ! {
+    let entry = entry as usize;
+    asm!(
+        "msr PSP, {psp}",
+        "mrs {tmp}, CONTROL",
+        "orrs {tmp}, {spsel}",
+        "msr CONTROL, {tmp}",
+        "isb",
+        "bx {entry}",
+        // `out(reg) _` is not permitted in a `noreturn` asm! call,
+        // so instead use `in(reg) 0` and don't restore it afterwards.
+        tmp = in(reg) 0,
+        spsel = in(reg) 2,
+        psp = in(reg) initial_psp,
+        entry = in(reg) entry,
+        options(noreturn, nomem, nostack),
+    );
+}
+
+#[cfg(test)]
+mod test;
+
+/// Sets the stack limit of the currently active stack
+///
+/// Writes `limit` to MSPLIM or PSPLIM, depending on which stack pointer is active (as selected
+/// by CONTROL.SPSEL; Handler mode always uses the main stack). Afterwards, any attempt to move
+/// the stack pointer below `limit` raises a UsageFault (STKOF), or a HardFault if UsageFault is
+/// disabled, which makes stack overflows detectable in hardware.
+///
+/// `limit` is rounded down to a multiple of 8 by the hardware.
+///
+/// # Safety
+///
+/// The active stack pointer must currently be above `limit`, and no code may need stack space
+/// below it.
+#[cfg(armv8m_main)]
+#[inline]
+pub unsafe fn set_stack_limit(limit: u32) {
+    match control::read().spsel() {
+        control::Spsel::Msp => msplim::write(limit),
+        control::Spsel::Psp => psplim::write(limit),
+    }
+}
+
+/// Disables the stack limit check of the currently active stack
+///
+/// See [`set_stack_limit`].
+#[cfg(armv8m_main)]
+#[inline]
+pub fn clear_stack_limit() {
+    // NOTE(unsafe) a limit of 0 disables the check, which can't cause a fault
+    unsafe { set_stack_limit(0) }
+}
+
+/// Returns `true` if the processor is executing an exception handler (Handler mode)
+///
+/// Reads the exception number from IPSR, which is nonzero while an exception is active. IPSR can
+/// be read from unprivileged code too. To find out which exception is active, use
+/// [`SCB::vect_active`](crate::peripheral::SCB::vect_active).
+#[cfg(cortex_m)]
+#[inline]
+pub fn in_handler_mode() -> bool {
+    let ipsr: u32;
+    unsafe { asm!("mrs {}, IPSR", out(reg) ipsr, options(nomem, nostack, preserves_flags)) };
+    ipsr & 0x1ff != 0
+}
+
+/// Returns `true` if the processor is executing application code (Thread mode)
+///
+/// See [`in_handler_mode`].
+#[cfg(cortex_m)]
+#[inline]
+pub fn in_thread_mode() -> bool {
+    !in_handler_mode()
+}
+
+/// Returns `true` if the current context has used the FPU, i.e. CONTROL.FPCA is set
+///
+/// A context switch only needs to save and restore the FPU registers when this returns `true`.
+/// On targets without an FPU this always returns `false`, so context switch code can use this
+/// function without `#[cfg]`s.
+#[cfg(cortex_m)]
+#[inline]
+pub fn fpu_context_active() -> bool {
+    #[cfg(has_fpu)]
+    {
+        control::read().fpca().is_active()
+    }
+
+    #[cfg(not(has_fpu))]
+    {
+        false
+    }
+}
+
+/// Fills the unused part of the current stack with `pattern`, for [`stack_high_water`]
+///
+/// Writes `pattern` to every word from just below the current stack pointer down to `limit`
+/// (inclusive), e.g. early in `main`. Interrupt handlers that run later and use the same stack
+/// overwrite the pattern too, so their usage is included in the measurement.
+///
+/// # Safety
+///
+/// - `limit` must be 4-byte aligned and be the lowest address of the active stack; the memory
+///   between `limit` and the stack pointer must not be used for anything else.
+/// - Anything living below the current stack pointer is overwritten. Don't call this while data
+///   below the stack pointer is still needed, e.g. from code that switched stacks and left data
+///   on the other one in the same memory region.
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn paint_stack(limit: u32, pattern: u32) {
+    let sp: u32;
+    asm!("mov {}, sp", out(reg) sp, options(nomem, nostack, preserves_flags));
+
+    let mut p = (sp & !0b11) as *mut u32;
+    let limit = limit as *mut u32;
+    while p > limit {
+        p = p.offset(-1);
+        core::ptr::write_volatile(p, pattern);
+    }
+}
+
+/// Returns the number of words above `limit` that still hold the `pattern` written by
+/// [`paint_stack`]
+///
+/// Scans upwards from `limit` and stops at the first word that was overwritten, or at the current
+/// stack pointer. The highest stack usage since the stack was painted, in bytes, is the distance
+/// from `limit` to the top of the stack minus 4 times the returned value.
+///
+/// *NOTE* A word that happens to have been written with the value `pattern` looks untouched, so
+/// the result is an upper bound of the free space; choose an unlikely pattern.
+///
+/// # Safety
+///
+/// `limit` must be the same value that was passed to [`paint_stack`].
+#[cfg(cortex_m)]
+#[inline]
+pub unsafe fn stack_high_water(limit: u32, pattern: u32) -> u32 {
+    let sp: u32;
+    asm!("mov {}, sp", out(reg) sp, options(nomem, nostack, preserves_flags));
+
+    let mut p = limit as *const u32;
+    let sp = sp as *const u32;
+    let mut count = 0;
+    while p < sp && core::ptr::read_volatile(p) == pattern {
+        p = p.offset(1);
+        count += 1;
+    }
+    count
+}
+
+/// Security state of the processor
+#[cfg(armv8m)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SecurityState {
+    /// Secure state
+    Secure,
+    /// Non-secure state
+    NonSecure,
+}
+
+/// Returns the security state the processor is currently executing in
+///
+/// There's no register that holds the current security state, so this executes a TT instruction
+/// on the address of this function: the response reports the address as Secure only when the
+/// instruction is executed in Secure state, and code always executes from memory of its own
+/// security state.
+///
+/// *NOTE* Without the Security Extension the processor always executes in Non-secure state, so
+/// this returns `NonSecure` on such devices.
+#[cfg(armv8m)]
+#[inline]
+pub fn security_state() -> SecurityState {
+    use crate::cmse::{AccessType, TestTarget};
+
+    let addr = security_state as *const () as *mut u32;
+    if TestTarget::check(addr, AccessType::Current).secure() {
+        SecurityState::Secure
+    } else {
+        SecurityState::NonSecure
+    }
+}
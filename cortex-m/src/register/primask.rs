@@ -13,6 +13,28 @@ pub enum Primask {
 }
 
 impl Primask {
+    /// Creates a `Primask` value from the raw contents of the register.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        if bits & (1 << 0) == (1 << 0) {
+            Primask::Inactive
+        } else {
+            Primask::Active
+        }
+    }
+
+    /// Returns the raw contents of the register: `0` if `Active`, `1` if `Inactive`
+    ///
+    /// Together with [`Primask::from_bits`] this allows passing the interrupt state to and from
+    /// code that represents it as a raw PRIMASK value, e.g. across an FFI boundary.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        match self {
+            Primask::Active => 0,
+            Primask::Inactive => 1,
+        }
+    }
+
     /// All exceptions with configurable priority are active
     #[inline]
     pub fn is_active(self) -> bool {
@@ -32,9 +54,13 @@ impl Primask {
 pub fn read() -> Primask {
     let r: u32;
     unsafe { asm!("mrs {}, PRIMASK", out(reg) r, options(nomem, nostack, preserves_flags)) };
-    if r & (1 << 0) == (1 << 0) {
-        Primask::Inactive
-    } else {
-        Primask::Active
+    Primask::from_bits(r)
+}
+
+#[cfg(cortex_m)]
+impl super::CoreRegister for Primask {
+    #[inline]
+    fn read() -> Self {
+        read()
     }
 }
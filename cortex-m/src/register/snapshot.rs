@@ -0,0 +1,117 @@
+//! Processor state snapshot
+
+#[cfg(cortex_m)]
+use core::arch::asm;
+
+use super::control::Control;
+#[cfg(not(any(armv6m, armv8m_base)))]
+use super::faultmask::Faultmask;
+use super::primask::Primask;
+
+/// The values of the special-purpose core registers at one point in time
+///
+/// Returned by [`snapshot`](super::snapshot). Useful for fault and panic dumps.
+#[derive(Clone, Copy, Debug)]
+pub struct CpuSnapshot {
+    /// Control register
+    pub control: Control,
+    /// Priority mask register
+    pub primask: Primask,
+    /// Base priority mask register (not available on ARMv6-M and ARMv8-M Baseline)
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    pub basepri: u8,
+    /// Fault mask register (not available on ARMv6-M and ARMv8-M Baseline)
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    pub faultmask: Faultmask,
+    /// Combined program status register (APSR, IPSR and EPSR)
+    pub xpsr: u32,
+    /// Main stack pointer
+    pub msp: u32,
+    /// Process stack pointer
+    pub psp: u32,
+}
+
+/// Reads all the special-purpose core registers
+///
+/// The registers are read in a fixed order (xPSR, CONTROL, PRIMASK, BASEPRI, FAULTMASK, PSP,
+/// MSP), each with its own `mrs` instruction, so the snapshot is not atomic with respect to
+/// interrupts. MSP is read last, and reflects the stack usage of this function.
+///
+/// On non Cortex-M targets this returns a snapshot with all registers zeroed, for testing.
+#[inline]
+pub fn snapshot() -> CpuSnapshot {
+    #[cfg(cortex_m)]
+    {
+        let xpsr;
+        unsafe { asm!("mrs {}, XPSR", out(reg) xpsr, options(nomem, nostack, preserves_flags)) };
+
+        CpuSnapshot {
+            control: super::control::read(),
+            primask: super::primask::read(),
+            #[cfg(not(any(armv6m, armv8m_base)))]
+            basepri: super::basepri::read(),
+            #[cfg(not(any(armv6m, armv8m_base)))]
+            faultmask: super::faultmask::read(),
+            xpsr,
+            psp: super::psp::read(),
+            msp: super::msp::read(),
+        }
+    }
+
+    #[cfg(not(cortex_m))]
+    {
+        CpuSnapshot {
+            control: Control::from_bits(0),
+            primask: Primask::from_bits(0),
+            #[cfg(not(any(armv6m, armv8m_base)))]
+            basepri: 0,
+            #[cfg(not(any(armv6m, armv8m_base)))]
+            faultmask: Faultmask::Active,
+            xpsr: 0,
+            msp: 0,
+            psp: 0,
+        }
+    }
+}
+
+/// Writes a [`snapshot`](super::snapshot) of the core registers to ITM stimulus port `port`
+///
+/// The registers are written as one line of text, e.g.
+/// `CONTROL=0x00000000 PRIMASK=0x00000001 BASEPRI=0x00000000 FAULTMASK=0x00000000 xPSR=0x01000003
+/// MSP=0x2000ffa0 PSP=0x00000000`. The values are formatted with
+/// [`itm::write_hex_u32`](crate::itm::write_hex_u32) instead of `core::fmt` and nothing is
+/// allocated, so this is cheap to call from a panic or fault handler.
+///
+/// The ITM must already be configured, and the port enabled.
+///
+/// *NOTE* Like the [`itm`](crate::itm) module, this is not available on ARMv6-M and ARMv8-M
+/// Baseline.
+///
+/// # Safety
+///
+/// The stimulus port is accessed without owning the [`ITM`](crate::peripheral::ITM): no other
+/// code may write to `port` while this function runs, e.g. call it with interrupts disabled.
+#[cfg(not(any(armv6m, armv8m_base)))]
+#[inline]
+pub unsafe fn dump_to_itm(port: u8) {
+    use crate::itm::{write_hex_u32, write_str};
+    use crate::peripheral::ITM;
+
+    let stim = &mut (*ITM::PTR).stim[usize::from(port)];
+    let snapshot = super::snapshot();
+
+    let mut field = |name: &str, value: u32| {
+        write_str(stim, name);
+        write_str(stim, "=0x");
+        write_hex_u32(stim, value);
+    };
+
+    field("CONTROL", snapshot.control.bits());
+    field(" PRIMASK", snapshot.primask.bits());
+    field(" BASEPRI", u32::from(snapshot.basepri));
+    field(" FAULTMASK", u32::from(snapshot.faultmask.is_inactive()));
+    field(" xPSR", snapshot.xpsr);
+    field(" MSP", snapshot.msp);
+    field(" PSP", snapshot.psp);
+    write_str(stim, "\n");
+}
@@ -0,0 +1,179 @@
+use crate::register::apsr::{Apsr, Condition};
+use crate::register::control::{Control, ControlBuilder, Fpca, Npriv, Spsel};
+use crate::register::primask::Primask;
+
+extern crate std;
+use std::format;
+
+#[test]
+fn apsr() {
+    let apsr = Apsr::from_bits(0);
+    assert!(!apsr.n() && !apsr.z() && !apsr.c() && !apsr.v() && !apsr.q());
+
+    assert!(Apsr::from_bits(1 << 31).n());
+    assert!(Apsr::from_bits(1 << 30).z());
+    assert!(Apsr::from_bits(1 << 29).c());
+    assert!(Apsr::from_bits(1 << 28).v());
+    assert!(Apsr::from_bits(1 << 27).q());
+
+    assert_eq!(
+        format!("{}", Apsr::from_bits(1 << 30 | 1 << 29)),
+        "APSR{N=0 Z=1 C=1 V=0 Q=0}"
+    );
+}
+
+#[test]
+fn apsr_condition_holds() {
+    const N: u32 = 1 << 31;
+    const Z: u32 = 1 << 30;
+    const C: u32 = 1 << 29;
+    const V: u32 = 1 << 28;
+
+    // flags, then the expected result for EQ, NE, CS, CC, MI, PL, VS, VC, HI, LS, GE, LT, GT, LE
+    let table: [(u32, [u8; 14]); 6] = [
+        (0, [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 1, 0]),
+        (Z, [1, 0, 0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 0, 1]),
+        (C, [0, 1, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 1, 0]),
+        (C | Z, [1, 0, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0, 0, 1]),
+        (N, [0, 1, 0, 1, 1, 0, 0, 1, 0, 1, 0, 1, 0, 1]),
+        (N | V, [0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 1, 0, 1, 0]),
+    ];
+
+    let conditions = [
+        Condition::Eq,
+        Condition::Ne,
+        Condition::Cs,
+        Condition::Cc,
+        Condition::Mi,
+        Condition::Pl,
+        Condition::Vs,
+        Condition::Vc,
+        Condition::Hi,
+        Condition::Ls,
+        Condition::Ge,
+        Condition::Lt,
+        Condition::Gt,
+        Condition::Le,
+    ];
+
+    for (flags, expected) in table {
+        let apsr = Apsr::from_bits(flags);
+        for (cond, &expected) in conditions.iter().zip(expected.iter()) {
+            assert_eq!(
+                apsr.condition_holds(*cond),
+                expected == 1,
+                "{:?} with {}",
+                cond,
+                apsr
+            );
+        }
+    }
+}
+
+#[test]
+fn control() {
+    let mut control = Control::thread_mode_default();
+    assert_eq!(control.bits(), 0);
+    assert_eq!(control.npriv(), Npriv::Privileged);
+    assert_eq!(control.spsel(), Spsel::Msp);
+    assert_eq!(control.fpca(), Fpca::NotActive);
+
+    control.set_npriv(Npriv::Unprivileged);
+    assert_eq!(control.bits(), 0b001);
+    control.set_spsel(Spsel::Psp);
+    assert_eq!(control.bits(), 0b011);
+    control.set_fpca(Fpca::Active);
+    assert_eq!(control.bits(), 0b111);
+
+    assert_eq!(control.npriv(), Npriv::Unprivileged);
+    assert_eq!(control.spsel(), Spsel::Psp);
+    assert_eq!(control.fpca(), Fpca::Active);
+
+    control.set_spsel(Spsel::Msp);
+    assert_eq!(control.bits(), 0b101);
+
+    assert_eq!(format!("{}", control), "CONTROL{nPRIV=1 SPSEL=0 FPCA=1}");
+}
+
+#[test]
+fn control_default() {
+    let control = Control::default();
+    assert_eq!(control.bits(), 0);
+    assert_eq!(control.npriv(), Npriv::default());
+    assert_eq!(control.spsel(), Spsel::default());
+    assert_eq!(control.fpca(), Fpca::default());
+}
+
+#[test]
+fn control_builder() {
+    assert_eq!(ControlBuilder::new().build().bits(), 0);
+
+    let control = ControlBuilder::new().unprivileged().use_psp().build();
+    assert_eq!(control.npriv(), Npriv::Unprivileged);
+    assert_eq!(control.spsel(), Spsel::Psp);
+    assert_eq!(control.bits(), 0b011);
+
+    let control = ControlBuilder::new()
+        .unprivileged()
+        .use_psp()
+        .privileged()
+        .use_msp()
+        .build();
+    assert_eq!(control.bits(), 0);
+}
+
+#[test]
+fn fpscr() {
+    use crate::register::fpscr::{Fpscr, RMode};
+
+    let mut fpscr = Fpscr::from_bits(0);
+    assert_eq!(fpscr.rmode(), RMode::Nearest);
+
+    fpscr.set_rmode(RMode::MinusInfinity);
+    assert_eq!(fpscr.bits(), 2 << 22);
+    fpscr.set_rmode(RMode::Zero);
+    assert_eq!(fpscr.bits(), 3 << 22);
+    fpscr.set_rmode(RMode::PlusInfinity);
+    assert_eq!(fpscr.bits(), 1 << 22);
+
+    fpscr.set_n(true);
+    fpscr.set_ioc(true);
+    assert!(fpscr.n() && fpscr.ioc());
+    assert_eq!(fpscr.bits(), 1 << 31 | 1 << 22 | 1 << 0);
+
+    assert_eq!(
+        format!("{}", fpscr),
+        "FPSCR{N=1 Z=0 C=0 V=0 AHP=0 DN=0 FZ=0 RMode=1 IDC=0 IXC=0 UFC=0 OFC=0 DZC=0 IOC=1}"
+    );
+}
+
+#[test]
+fn primask() {
+    assert_eq!(Primask::from_bits(0), Primask::Active);
+    assert_eq!(Primask::from_bits(1), Primask::Inactive);
+
+    for primask in [Primask::Active, Primask::Inactive] {
+        assert_eq!(Primask::from_bits(primask.bits()), primask);
+    }
+}
+
+#[test]
+fn snapshot() {
+    let snapshot = crate::register::snapshot();
+
+    assert_eq!(snapshot.control.bits(), 0);
+    assert_eq!(snapshot.primask, Primask::Active);
+    assert_eq!(snapshot.xpsr, 0);
+    assert_eq!(snapshot.msp, 0);
+    assert_eq!(snapshot.psp, 0);
+}
+
+#[test]
+fn const_from_bits() {
+    const APSR: Apsr = Apsr::from_bits(1 << 30);
+    const CONTROL: Control = Control::from_bits(0b10);
+
+    assert!(APSR.z());
+    assert_eq!(CONTROL.spsel(), Spsel::Psp);
+    assert_eq!(CONTROL.bits(), 0b10);
+}
@@ -0,0 +1,103 @@
+//! A count down timer based on SysTick.
+
+use crate::peripheral::{syst::SystClkSource, SYST};
+use embedded_hal::timer::CountDown;
+use void::Void;
+
+/// System timer (SysTick) as a non-blocking count down timer.
+///
+/// Unlike [`Delay`](crate::delay::Delay), which blocks until the time has elapsed, this timer
+/// is started with [`CountDown::start`] and then polled with [`CountDown::wait`], which returns
+/// `Err(nb::Error::WouldBlock)` until the time has elapsed. This makes it usable as a timeout
+/// source by generic code.
+///
+/// Durations are given in µs. Durations that don't fit in the 24-bit counter are counted as
+/// several wraps of the counter; `wait` must then be called at least once per wrap (every
+/// `0x0100_0000` clock cycles), since only one wrap is detected between two calls.
+pub struct Timer {
+    syst: SYST,
+    frequency: u32,
+    full_cycles: u32,
+    last: u32,
+}
+
+impl Timer {
+    /// Configures the system timer (SysTick) as a count down timer.
+    ///
+    /// `ahb_frequency` is a frequency of the AHB bus in Hz.
+    #[inline]
+    pub fn new(syst: SYST, ahb_frequency: u32) -> Self {
+        Self::with_source(syst, ahb_frequency, SystClkSource::Core)
+    }
+
+    /// Configures the system timer (SysTick) as a count down timer
+    /// with a clock source.
+    ///
+    /// `frequency` is the frequency of your `clock_source` in Hz.
+    #[inline]
+    pub fn with_source(mut syst: SYST, frequency: u32, clock_source: SystClkSource) -> Self {
+        syst.set_clock_source(clock_source);
+
+        Timer {
+            syst,
+            frequency,
+            full_cycles: 0,
+            last: 0,
+        }
+    }
+
+    /// Releases the system timer (SysTick) resource.
+    #[inline]
+    pub fn free(self) -> SYST {
+        self.syst
+    }
+}
+
+impl CountDown for Timer {
+    /// Duration in µs
+    type Time = u32;
+
+    #[inline]
+    fn start<T>(&mut self, count: T)
+    where
+        T: Into<u32>,
+    {
+        let ticks = u64::from(count.into()) * u64::from(self.frequency) / 1_000_000;
+
+        self.full_cycles = (ticks >> 24) as u32;
+        self.last = (ticks & 0xffffff) as u32;
+
+        if self.full_cycles > 0 {
+            self.syst.set_reload(0xffffff);
+        } else {
+            self.syst.set_reload(self.last.max(2) - 1);
+        }
+        self.syst.clear_current();
+        self.syst.enable_counter();
+    }
+
+    #[inline]
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        if !self.syst.has_wrapped() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if self.full_cycles > 0 {
+            self.full_cycles -= 1;
+            if self.full_cycles > 0 {
+                return Err(nb::Error::WouldBlock);
+            }
+
+            // count the remaining ticks with a single, shorter wrap
+            if self.last > 1 {
+                self.syst.set_reload(self.last - 1);
+                self.syst.clear_current();
+                self.last = 0;
+                return Err(nb::Error::WouldBlock);
+            }
+        }
+
+        self.syst.disable_counter();
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! Trace setup
+//!
+//! Configures the DCB, TPIU, ITM and DWT together so that ITM output (e.g. [`iprintln!`]) and
+//! DWT packets come out of the Serial Wire Output (SWO) pin.
+//!
+//! **NOTE** This module is only available on ARMv7-M and newer.
+
+use crate::peripheral::dwt::SyncTap;
+use crate::peripheral::itm::ITMSettings;
+use crate::peripheral::tpiu::TraceProtocol;
+use crate::peripheral::{DCB, DWT, ITM, TPIU};
+
+/// Trace configuration applied by [`configure`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct TraceConfig {
+    /// Frequency of the TPIU asynchronous reference clock in Hz, usually the core clock.
+    pub ref_clk_hz: u32,
+    /// Baud rate of the SWO output.
+    pub swo_baud: u32,
+    /// Protocol of the trace output.
+    pub protocol: TraceProtocol,
+    /// ITM trace control settings, see [`ITM::configure`].
+    pub itm: ITMSettings,
+    /// Enabled stimulus ports `0..32`: bit `n` enables port `n`.
+    pub ports: u32,
+    /// Rate of the synchronization packets, see [`DWT::set_sync_count_tap`].
+    ///
+    /// The cycle counter is enabled unless this is [`SyncTap::Disabled`].
+    pub sync: SyncTap,
+}
+
+/// Possible error values returned by [`configure`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum TraceError {
+    /// The SWO baud rate is zero.
+    ZeroBaudRate,
+    /// The SWO baud rate is higher than the reference clock frequency.
+    BaudRateTooHigh,
+    /// The SWO baud rate is so low that the prescaler doesn't fit in the 16-bit ACPR register.
+    BaudRateTooLow,
+}
+
+/// Configures tracing over SWO
+///
+/// Performs the whole setup sequence in the required order:
+///
+/// 1. Enables trace in DEMCR (TRCENA), without which the other peripherals ignore writes.
+/// 2. Sets the TPIU output protocol and the SWO baud rate prescaler. The formatter is only
+///    enabled for the parallel trace port, which needs it.
+/// 3. Unlocks the ITM, applies `config.itm`, enables synchronization packets if requested and
+///    enables the stimulus ports in `config.ports`.
+/// 4. Unlocks the DWT and sets the synchronization packet rate, enabling the cycle counter that
+///    drives it.
+///
+/// Returns an error without touching any peripheral if the baud rate can't be produced from the
+/// reference clock.
+#[allow(clippy::missing_inline_in_public_items)]
+pub fn configure(
+    dcb: &mut DCB,
+    dwt: &mut DWT,
+    itm: &mut ITM,
+    tpiu: &mut TPIU,
+    config: TraceConfig,
+) -> Result<(), TraceError> {
+    if config.swo_baud == 0 {
+        return Err(TraceError::ZeroBaudRate);
+    }
+    if config.swo_baud > config.ref_clk_hz {
+        return Err(TraceError::BaudRateTooHigh);
+    }
+    if config.ref_clk_hz / config.swo_baud - 1 > 0xffff {
+        return Err(TraceError::BaudRateTooLow);
+    }
+
+    dcb.enable_trace();
+
+    tpiu.set_trace_output_protocol(config.protocol);
+    tpiu.set_swo_baud_rate(config.ref_clk_hz, config.swo_baud);
+    tpiu.enable_continuous_formatting(config.protocol == TraceProtocol::Parallel);
+
+    itm.unlock();
+    itm.configure(config.itm);
+    itm.enable_sync_packets(config.sync != SyncTap::Disabled);
+    itm.enable_ports(0, config.ports);
+
+    DWT::unlock();
+    dwt.set_sync_count_tap(config.sync);
+    if config.sync != SyncTap::Disabled {
+        dwt.enable_cycle_counter();
+    }
+
+    Ok(())
+}
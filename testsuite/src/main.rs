@@ -26,6 +26,92 @@ mod tests {
         assert!(cortex_m::Peripherals::take().is_none());
     }
 
+    #[test]
+    fn interrupt_free() {
+        use cortex_m::register::primask;
+
+        assert!(primask::read().is_active());
+        cortex_m::interrupt::free(|| {
+            assert!(primask::read().is_inactive());
+
+            // nested critical sections must not re-enable interrupts on exit
+            cortex_m::interrupt::free(|| {});
+            assert!(primask::read().is_inactive());
+        });
+        assert!(primask::read().is_active());
+    }
+
+    #[test]
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    fn interrupt_free_preserve_all() {
+        use cortex_m::register::{faultmask, primask};
+
+        assert!(faultmask::read().is_active());
+        cortex_m::interrupt::free_preserve_all(|| {
+            assert!(primask::read().is_inactive());
+            assert!(faultmask::read().is_inactive());
+        });
+        assert!(faultmask::read().is_active());
+        assert!(primask::read().is_active());
+    }
+
+    #[test]
+    fn control_spsel_round_trip() {
+        use core::arch::asm;
+        use cortex_m::register::control::{self, Control, Spsel};
+
+        assert_eq!(control::read().spsel(), Spsel::Msp);
+
+        // Switch to PSP and back in a single asm block, with PSP set up to the current stack
+        // pointer, so the stack is not touched while PSP is active
+        let (on_psp, on_msp): (u32, u32);
+        unsafe {
+            asm!(
+                "mov {tmp}, sp",
+                "msr PSP, {tmp}",
+                "mrs {tmp}, CONTROL",
+                "orrs {tmp}, {spsel}",
+                "msr CONTROL, {tmp}",
+                "isb",
+                "mrs {on_psp}, CONTROL",
+                "bics {tmp}, {spsel}",
+                "msr CONTROL, {tmp}",
+                "isb",
+                "mrs {on_msp}, CONTROL",
+                tmp = out(reg) _,
+                spsel = in(reg) 2,
+                on_psp = out(reg) on_psp,
+                on_msp = out(reg) on_msp,
+                options(nomem),
+            );
+        }
+
+        assert_eq!(Control::from_bits(on_psp).spsel(), Spsel::Psp);
+        assert_eq!(Control::from_bits(on_msp).spsel(), Spsel::Msp);
+        assert_eq!(control::read().spsel(), Spsel::Msp);
+    }
+
+    #[test]
+    #[cfg(not(any(armv6m, armv8m_base)))]
+    fn basepri_round_trip() {
+        use cortex_m::register::basepri;
+
+        // unimplemented priority bits read as zero
+        unsafe { basepri::write(0xff) };
+        let implemented = basepri::read();
+        assert_ne!(implemented, 0);
+
+        for prio in 0..=255u8 {
+            if prio & !implemented == 0 {
+                unsafe { basepri::write(prio) };
+                assert_eq!(basepri::read(), prio);
+            }
+        }
+
+        unsafe { basepri::write(0) };
+        assert_eq!(basepri::read(), 0);
+    }
+
     #[test]
     #[cfg(not(feature = "semihosting"))] // QEMU does not model the cycle counter
     fn cycle_count(p: &mut cortex_m::Peripherals) {